@@ -0,0 +1,536 @@
+use crate::{
+    ConjugatedForm, Coordinator, FinalPersonPrefix, FiniteVerbalForm, LocativePrefix, MarkerName,
+    MiddlePrefix, Person, Preformative, PERSONS,
+};
+use std::collections::{HashMap, HashSet};
+
+/*
+    The analyzer works backwards from a surface form: the form is split into prefixes,
+    a stem and suffixes, and every configuration of the slots that could produce
+    these prefixes and suffixes is printed and kept if it renders the surface form.
+    A slot is only tried if one of its forms appears in the prefixes (or the suffixes),
+    which keeps the number of configurations to print small. A configuration is dropped
+    if one of its prefixes can be removed without changing the surface form, and only
+    one configuration is kept for each segmentation and gloss.
+*/
+
+// every letter found in the surface forms of the prefixes
+const PREFIX_LETTERS: [char; 15] = [
+    'n', 'u', 'ḫ', 'a', 'ē', 'i', 'g', 'm', 'b', 'ʔ', 'e', 'r', 'd', 't', 'š',
+];
+
+// Returns every configuration that `print` renders as the surface form
+pub fn analyze(surface: &str) -> Vec<FiniteVerbalForm> {
+    let letters: Vec<char> = surface.chars().collect();
+    let mut analyses = Vec::new();
+    let mut segmentations = HashSet::new();
+    let mut rendered_suffixes = HashMap::new();
+
+    for start in 0..letters.len() {
+        let prefixes: String = letters[..start].iter().collect();
+        if !prefixes
+            .chars()
+            .all(|letter| PREFIX_LETTERS.contains(&letter))
+        {
+            // a longer sequence of prefixes would contain the same letter
+            break;
+        }
+        // the prefixes rendered for a final person-prefix, only the initial /u/ of the stem
+        // changes them otherwise (24.3)
+        let mut rendered_prefixes: Vec<(PrefixContext, Vec<FiniteVerbalForm>)> = Vec::new();
+        for end in (start + 1)..=letters.len() {
+            let stem: String = letters[start..end].iter().collect();
+            let suffixes: String = letters[end..].iter().collect();
+            // the suffixes only depend on the last letter of the stem
+            let key = (end, letters[end - 1]);
+            let configurations = rendered_suffixes
+                .entry(key)
+                .or_insert_with(|| suffix_configurations(&stem, &suffixes))
+                .iter()
+                .map(|verb| {
+                    let mut verb = verb.clone();
+                    verb.slot_12 = stem.clone();
+                    verb
+                })
+                .collect();
+            for (representative, group) in argument_groups(configurations) {
+                let context = prefix_context(&representative);
+                let position = rendered_prefixes
+                    .iter()
+                    .position(|(other, _)| *other == context)
+                    .unwrap_or_else(|| {
+                        let configurations = prefix_configurations(&representative, &prefixes)
+                            .into_iter()
+                            .filter(|verb| renders_prefixes(verb, &prefixes))
+                            .collect();
+                        rendered_prefixes.push((context, configurations));
+                        rendered_prefixes.len() - 1
+                    });
+                for prefix_slots in rendered_prefixes[position].1.iter() {
+                    for arguments in group.iter() {
+                        let mut verb = with_prefixes(arguments, prefix_slots);
+                        let Ok(form) = verb.print_segmented() else {
+                            continue;
+                        };
+                        if form.surface() == surface
+                            && !has_silent_locative(&verb, &form)
+                            && !has_silent_prefix(&verb, surface)
+                            && segmentations.insert((form.hyphenated(), form.gloss()))
+                        {
+                            analyses.push(verb);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    analyses
+}
+
+// Aspect, transitivity, arguments, {ed} marker and subordinator,
+// kept if the slots after the stem render the suffixes
+fn suffix_configurations(stem: &str, suffixes: &str) -> Vec<FiniteVerbalForm> {
+    let mut candidates = Vec::new();
+    for is_perfective in [true, false] {
+        for is_transitive in [false, true] {
+            let verb = FiniteVerbalForm::new(stem.to_string(), is_perfective, is_transitive);
+            for subject in PERSONS {
+                // the third person patients have no suffix of their own, the non-human one
+                // comes first so that it is kept for the segmentations they share
+                let objects = if is_transitive {
+                    [Person::ThirdSingNonHuman]
+                        .into_iter()
+                        .chain(
+                            PERSONS
+                                .into_iter()
+                                .filter(|person| !matches!(person, Person::ThirdSingNonHuman)),
+                        )
+                        .map(Some)
+                        .chain([None])
+                        .collect()
+                } else {
+                    vec![None]
+                };
+                for object in objects {
+                    let mut arguments = verb.clone();
+                    arguments.set_subject(subject.clone());
+                    arguments.object = object;
+                    candidates.push(arguments);
+                }
+            }
+        }
+    }
+
+    candidates = expand(candidates, |verb| {
+        let mut variants = vec![verb.clone()];
+        if !verb.is_perfective && suffixes.contains('d') {
+            variants.push(with(verb, |verb| {
+                verb.set_ed_marker(Some("ed".to_string()));
+            }));
+        }
+        variants
+    });
+    candidates = expand(candidates, |verb| {
+        let mut variants = vec![verb.clone()];
+        if suffixes.ends_with('a') {
+            variants.push(with(verb, |verb| {
+                verb.set_subordinator(true);
+            }));
+        }
+        variants
+    });
+
+    candidates
+        .into_iter()
+        .filter(|verb| {
+            verb.clone().print_segmented().is_ok_and(|form| {
+                let rendered: String = form
+                    .morphs
+                    .iter()
+                    .filter(|morph| morph.slot.position() > MarkerName::Stem.position())
+                    .map(|morph| morph.surface.clone())
+                    .collect();
+                rendered == suffixes
+            })
+        })
+        .collect()
+}
+
+// Groups the arguments resolved into the same final person-prefix, which is all
+// the prefixes depend on, the first configuration of each group representing it
+fn argument_groups(
+    configurations: Vec<FiniteVerbalForm>,
+) -> Vec<(FiniteVerbalForm, Vec<FiniteVerbalForm>)> {
+    let mut groups: Vec<(FiniteVerbalForm, Vec<FiniteVerbalForm>)> = Vec::new();
+    for arguments in configurations {
+        let Ok(resolved) = arguments.resolve_arguments() else {
+            continue;
+        };
+        let position = groups.iter().position(|(representative, _)| {
+            representative.is_perfective == arguments.is_perfective
+                && representative.is_transitive == arguments.is_transitive
+                && representative
+                    .resolve_arguments()
+                    .is_ok_and(|other| other.slot_11 == resolved.slot_11)
+        });
+        match position {
+            Some(position) => groups[position].1.push(arguments),
+            None => groups.push((arguments.clone(), vec![arguments])),
+        }
+    }
+    groups
+}
+
+// aspect, transitivity, final person-prefix and initial /u/ of the stem
+type PrefixContext = (bool, bool, Option<FinalPersonPrefix>, bool);
+
+fn prefix_context(verb: &FiniteVerbalForm) -> PrefixContext {
+    (
+        verb.is_perfective,
+        verb.is_transitive,
+        verb.resolve_arguments()
+            .ok()
+            .and_then(|resolved| resolved.slot_11),
+        verb.slot_12.starts_with('u'),
+    )
+}
+
+// Whether the slots before the stem render the prefixes
+fn renders_prefixes(verb: &FiniteVerbalForm, prefixes: &str) -> bool {
+    verb.clone().print_segmented().is_ok_and(|form| {
+        let rendered: String = form
+            .morphs
+            .iter()
+            .filter(|morph| morph.slot.position() < MarkerName::Stem.position())
+            .map(|morph| morph.surface.clone())
+            .collect();
+        rendered == prefixes
+    })
+}
+
+// The arguments with the slots 1 to 10 of another configuration
+fn with_prefixes(arguments: &FiniteVerbalForm, prefixes: &FiniteVerbalForm) -> FiniteVerbalForm {
+    let mut verb = arguments.clone();
+    verb.oblique_object = prefixes.oblique_object.clone();
+    verb.slot_1 = prefixes.slot_1.clone();
+    verb.slot_2 = prefixes.slot_2.clone();
+    verb.slot_3 = prefixes.slot_3.clone();
+    verb.slot_4 = prefixes.slot_4.clone();
+    verb.slot_5 = prefixes.slot_5.clone();
+    verb.slot_6 = prefixes.slot_6.clone();
+    verb.slot_7 = prefixes.slot_7.clone();
+    verb.slot_8 = prefixes.slot_8.clone();
+    verb.slot_9 = prefixes.slot_9.clone();
+    verb.slot_10 = prefixes.slot_10.clone();
+    verb
+}
+
+// Every filling of the slots 1 to 10 whose forms appear in the prefixes
+fn prefix_configurations(arguments: &FiniteVerbalForm, prefixes: &str) -> Vec<FiniteVerbalForm> {
+    let realizes = |forms: &[&str]| forms.iter().any(|form| prefixes.contains(form));
+    let mut candidates = vec![arguments.clone()];
+
+    candidates = expand(candidates, |verb| {
+        let mut variants = vec![verb.clone()];
+        if prefixes.starts_with("nu") {
+            variants.push(with(verb, |verb| {
+                verb.set_negative();
+            }));
+        }
+        if prefixes.starts_with("ḫa") || prefixes.starts_with("ḫē") {
+            variants.push(with(verb, |verb| {
+                verb.set_modal();
+            }));
+        }
+        variants
+    });
+    candidates = expand(candidates, |verb| {
+        let mut variants = vec![verb.clone()];
+        // 24.3 the vocalic prefixes may assimilate to /u/ or contract with /ḫa/
+        for (preformative, forms) in [
+            (Preformative::A, &["a", "u"][..]),
+            (Preformative::I, &["i", "u", "ē"][..]),
+            (Preformative::U, &["u"][..]),
+        ] {
+            if realizes(forms) {
+                variants.push(with(verb, |verb| {
+                    verb.set_preformative(Some(preformative));
+                }));
+            }
+        }
+        variants
+    });
+    candidates = expand(candidates, |verb| {
+        let mut variants = vec![verb.clone()];
+        if realizes(&["nga"]) {
+            variants.push(with(verb, |verb| {
+                verb.set_coordinator(Some(Coordinator));
+            }));
+        }
+        variants
+    });
+    candidates = expand(candidates, |verb| {
+        let mut variants = vec![verb.clone()];
+        if realizes(&["m"]) {
+            variants.push(with(verb, |verb| {
+                verb.set_ventive();
+            }));
+        }
+        variants
+    });
+    candidates = expand(candidates, |verb| {
+        let mut variants = vec![verb.clone()];
+        // 21.2 {ba} becomes /ma/ after the ventive
+        if realizes(&["ba", "ma"]) {
+            variants.push(with(verb, |verb| {
+                verb.set_middle_prefix(Some(MiddlePrefix));
+            }));
+        }
+        variants
+    });
+    candidates = expand(candidates, |verb| {
+        let mut variants = vec![verb.clone()];
+        for person in PERSONS {
+            if realizes(dative_forms(&person)) {
+                variants.push(with(verb, |verb| {
+                    verb.set_indirect_object(person);
+                }));
+            }
+        }
+        variants
+    });
+    candidates = expand(candidates, |verb| {
+        let mut variants = vec![verb.clone()];
+        for person in PERSONS
+            .into_iter()
+            .filter(|person| realizes(initial_forms(person)))
+        {
+            if realizes(&["da"]) {
+                variants.push(with(verb, |verb| {
+                    verb.set_comitative(Some(person.clone()));
+                }));
+            }
+            if realizes(&["ta"]) {
+                variants.push(with(verb, |verb| {
+                    verb.set_ablative(Some(person.clone()));
+                }));
+            }
+            if realizes(&["ši"]) {
+                variants.push(with(verb, |verb| {
+                    verb.set_terminative(Some(person.clone()));
+                }));
+            }
+            if realizes(&["da"]) && realizes(&["ta"]) {
+                variants.push(with(verb, |verb| {
+                    verb.set_comitative(Some(person.clone()))
+                        .set_ablative(Some(person.clone()));
+                }));
+            }
+            if realizes(&["da"]) && realizes(&["ši"]) {
+                variants.push(with(verb, |verb| {
+                    verb.set_comitative(Some(person.clone()))
+                        .set_terminative(Some(person.clone()));
+                }));
+            }
+        }
+        variants
+    });
+    candidates = expand(candidates, |verb| {
+        let mut variants = vec![verb.clone()];
+        if realizes(&["n"]) {
+            variants.push(with(verb, |verb| {
+                verb.set_locative_in(None);
+            }));
+        }
+        // 20.1 the locatives may be expressed with the initial person-prefix, which is
+        // shared with the comitative and the adverbial prefixes when they are present
+        let initial_persons: Vec<Option<Person>> = match verb.slot_6 {
+            Some(_) => vec![None],
+            None => PERSONS
+                .into_iter()
+                .filter(|person| realizes(initial_forms(person)))
+                .map(Some)
+                .collect(),
+        };
+        for person in initial_persons {
+            variants.push(with(verb, |verb| {
+                if let Some(person) = person.clone() {
+                    verb.set_initial_person_prefix(Some(person));
+                }
+                verb.set_locative_in(Some(Person::ThirdSingNonHuman));
+            }));
+            if realizes(&["bi", "mi"]) {
+                variants.push(with(verb, |verb| {
+                    if let Some(person) = person {
+                        verb.set_initial_person_prefix(Some(person));
+                    }
+                    verb.set_locative_on(Some(Person::ThirdSingNonHuman));
+                }));
+            }
+        }
+        if realizes(&["e"]) {
+            variants.push(with(verb, |verb| {
+                verb.set_locative_on(None);
+            }));
+        }
+        // {bi} without a separate initial person-prefix
+        if verb.slot_6.is_none() && realizes(&["bi", "mi"]) {
+            variants.push(with(verb, |verb| {
+                verb.set_locative_on(Some(Person::ThirdSingNonHuman));
+            }));
+        }
+        if realizes(&["ri"]) {
+            variants.push(with(verb, |verb| {
+                verb.set_oblique_object(Some(Person::SecondSing));
+            }));
+        }
+        // 13.1 the other oblique objects are expressed by the final person-prefix,
+        // which is only free in intransitive verbs
+        if !verb.is_transitive {
+            for person in PERSONS.into_iter().filter(|person| {
+                !matches!(person, Person::SecondSing) && realizes(final_forms(person))
+            }) {
+                variants.push(with(verb, |verb| {
+                    verb.set_oblique_object(Some(person));
+                }));
+            }
+        }
+        variants
+    });
+
+    candidates
+}
+
+// Whether the verb renders the same surface form without one of its prefixes
+fn has_silent_prefix(verb: &FiniteVerbalForm, surface: &str) -> bool {
+    let mut variants = Vec::new();
+    if verb.slot_1.is_some() {
+        variants.push(with(verb, |verb| verb.slot_1 = None));
+    }
+    if verb.slot_2.is_some() {
+        variants.push(with(verb, |verb| {
+            verb.set_preformative(None);
+        }));
+    }
+    if verb.slot_3.is_some() {
+        variants.push(with(verb, |verb| {
+            verb.set_coordinator(None);
+        }));
+    }
+    if verb.slot_4.is_some() {
+        variants.push(with(verb, |verb| verb.slot_4 = None));
+    }
+    if verb.slot_5.is_some() {
+        variants.push(with(verb, |verb| {
+            verb.set_middle_prefix(None);
+        }));
+    }
+    if verb.slot_7.is_some() {
+        variants.push(with(verb, |verb| verb.slot_7 = None));
+    }
+    if verb.slot_8.is_some() {
+        variants.push(with(verb, |verb| {
+            verb.slot_8 = None;
+            release_initial_person(verb);
+        }));
+    }
+    if verb.slot_9.is_some() {
+        variants.push(with(verb, |verb| {
+            verb.set_adverbial_prefix(None);
+            release_initial_person(verb);
+        }));
+    }
+    if verb.slot_10.is_some() {
+        variants.push(with(verb, |verb| {
+            verb.set_oblique_object(None);
+            release_initial_person(verb);
+        }));
+    }
+    variants
+        .into_iter()
+        .any(|mut variant| variant.print().is_ok_and(|form| form == surface))
+}
+
+// 20.1 a locative expressed by the initial or the final person-prefix
+// may be lost with this prefix, it is then not part of the analysis
+fn has_silent_locative(verb: &FiniteVerbalForm, form: &ConjugatedForm) -> bool {
+    let slots = match verb.slot_10 {
+        None => return false,
+        Some(LocativePrefix::InWithInitialPerson) => vec![
+            MarkerName::LocativePrefix,
+            MarkerName::InitialPronominalPrefix,
+        ],
+        Some(LocativePrefix::ObliqueWithFinalPerson) => {
+            vec![MarkerName::LocativePrefix, MarkerName::FinalPersonPrefix]
+        }
+        Some(_) => vec![MarkerName::LocativePrefix],
+    };
+    !form
+        .morphs
+        .iter()
+        .any(|morph| slots.contains(&morph.slot) && !morph.surface.is_empty())
+}
+
+// Removes the initial person-prefix once no dimensional prefix refers to it
+fn release_initial_person(verb: &mut FiniteVerbalForm) {
+    if verb.slot_8.is_none()
+        && verb.slot_9.is_none()
+        && !matches!(
+            verb.slot_10,
+            Some(LocativePrefix::InWithInitialPerson | LocativePrefix::OnWithInitialPerson)
+        )
+    {
+        verb.set_initial_person_prefix(None);
+    }
+}
+
+// Replaces every candidate with its variants
+fn expand(
+    candidates: Vec<FiniteVerbalForm>,
+    variants: impl Fn(&FiniteVerbalForm) -> Vec<FiniteVerbalForm>,
+) -> Vec<FiniteVerbalForm> {
+    candidates.iter().flat_map(variants).collect()
+}
+
+fn with(verb: &FiniteVerbalForm, set: impl FnOnce(&mut FiniteVerbalForm)) -> FiniteVerbalForm {
+    let mut verb = verb.clone();
+    set(&mut verb);
+    verb
+}
+
+// 17.2.1 {ba} becomes /ma/ after the ventive
+fn dative_forms(person: &Person) -> &'static [&'static str] {
+    match person {
+        Person::FirstSing => &["ma"],
+        Person::SecondSing | Person::SecondPlur => &["ra"],
+        Person::ThirdSingHuman => &["nna"],
+        Person::ThirdSingNonHuman | Person::ThirdPlurNonHuman => &["ba", "ma"],
+        Person::FirstPlur => &["mē"],
+        Person::ThirdPlurHuman => &["nnē"],
+    }
+}
+
+// 13.2.4 the prefix {e} may contract with a preceding vowel
+fn final_forms(person: &Person) -> &'static [&'static str] {
+    match person {
+        Person::FirstSing | Person::FirstPlur => &["ʔ"],
+        Person::SecondSing | Person::SecondPlur => &["e", "a", "i", "u"],
+        Person::ThirdSingHuman | Person::ThirdPlurHuman => &["n", "b"],
+        Person::ThirdSingNonHuman | Person::ThirdPlurNonHuman => &["b"],
+    }
+}
+
+// the singular initial person-prefixes may contract or be lost (16.2.4, 22.4)
+fn initial_forms(person: &Person) -> &'static [&'static str] {
+    match person {
+        Person::FirstSing => &["ʔ"],
+        Person::SecondSing => &["e", "a", "i", "u"],
+        Person::ThirdSingHuman => &["n"],
+        // {b} is lost after the /m/ of the ventive
+        Person::ThirdSingNonHuman | Person::ThirdPlurNonHuman => &["b", "m"],
+        Person::FirstPlur => &["mē"],
+        Person::SecondPlur => &["enē"],
+        Person::ThirdPlurHuman => &["nnē"],
+    }
+}
@@ -0,0 +1,63 @@
+use std::io::{BufRead, Write};
+use std::process::ExitCode;
+use sumerian_conjugator::cli;
+use sumerian_conjugator::repl::Session;
+use sumerian_conjugator::ConjugationError;
+
+// e.g. sumerian-conjugate zu --perfective --transitive --subject 3sg.h --object 3sg.nh --ventive
+fn run() -> Result<(), ConjugationError> {
+    let options = cli::parse_args(std::env::args().skip(1))?;
+    if options.interactive && !options.help {
+        return interact(&options);
+    }
+    if options.help || options.stem.is_empty() {
+        print!("{}", cli::usage());
+        return Ok(());
+    }
+
+    let form = options.verb()?.print_segmented()?;
+    println!("{}", form.surface());
+    if options.segmented {
+        println!("{}", form.hyphenated());
+    }
+    if options.gloss {
+        println!("{}", form.gloss());
+    }
+    Ok(())
+}
+
+// reads the commands of the session until "quit" or the end of the input
+fn interact(options: &cli::Options) -> Result<(), ConjugationError> {
+    let mut session = match options.stem.is_empty() {
+        true => Session::default(),
+        false => {
+            let session = Session::from_verb(options.verb()?);
+            println!("{}", session.render()?);
+            session
+        }
+    };
+    let mut lines = std::io::stdin().lock().lines();
+    while !session.finished {
+        print!("> ");
+        let _ = std::io::stdout().flush();
+        let Some(Ok(line)) = lines.next() else {
+            break;
+        };
+        match session.execute(&line) {
+            Ok(output) if output.is_empty() => {}
+            Ok(output) => println!("{}", output.trim_end()),
+            Err(error) => println!("{}", error),
+        }
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
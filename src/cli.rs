@@ -0,0 +1,366 @@
+use crate::{
+    ConjugationError, Coordinator, FiniteVerbalForm, IpfvStem, LocativePrefix, MiddlePrefix,
+    Person, Preformative,
+};
+
+/*
+    Commands shared by the command-line tools, each of them calling a setter of FiniteVerbalForm.
+    On the command line they are written as options (--subject 3sg.h).
+*/
+
+pub struct Command {
+    pub name: &'static str,
+    pub argument: Option<&'static str>,
+    pub description: &'static str,
+}
+
+const PERSON: Option<&str> = Some("PERSON");
+
+pub const COMMANDS: [Command; 25] = [
+    Command {
+        name: "perfective",
+        argument: None,
+        description: "perfective (ḫamṭu) aspect",
+    },
+    Command {
+        name: "imperfective",
+        argument: None,
+        description: "imperfective (marû) aspect",
+    },
+    Command {
+        name: "reduplicate",
+        argument: None,
+        description: "imperfective aspect with a reduplicated stem",
+    },
+    Command {
+        name: "ed",
+        argument: None,
+        description: "imperfective aspect with the {ed} marker",
+    },
+    Command {
+        name: "imperfective-stem",
+        argument: Some("STEM"),
+        description: "imperfective aspect with another stem",
+    },
+    Command {
+        name: "transitive",
+        argument: None,
+        description: "transitive verb",
+    },
+    Command {
+        name: "intransitive",
+        argument: None,
+        description: "intransitive verb",
+    },
+    Command {
+        name: "subject",
+        argument: PERSON,
+        description: "subject of the verb",
+    },
+    Command {
+        name: "object",
+        argument: PERSON,
+        description: "object of a transitive verb",
+    },
+    Command {
+        name: "oblique",
+        argument: PERSON,
+        description: "oblique object (locative3)",
+    },
+    Command {
+        name: "negative",
+        argument: None,
+        description: "negative prefix {nu}",
+    },
+    Command {
+        name: "modal",
+        argument: None,
+        description: "modal prefix {ḫa}",
+    },
+    Command {
+        name: "preformative",
+        argument: Some("a|i|u"),
+        description: "preformative (finite-marker) prefix",
+    },
+    Command {
+        name: "coordinator",
+        argument: None,
+        description: "coordinator prefix {nga}",
+    },
+    Command {
+        name: "ventive",
+        argument: None,
+        description: "ventive prefix {mu}",
+    },
+    Command {
+        name: "middle",
+        argument: None,
+        description: "middle prefix {ba}",
+    },
+    Command {
+        name: "initial-person",
+        argument: PERSON,
+        description: "initial person-prefix",
+    },
+    Command {
+        name: "dative",
+        argument: PERSON,
+        description: "dative prefix of the indirect object",
+    },
+    Command {
+        name: "comitative",
+        argument: PERSON,
+        description: "comitative prefix {da}",
+    },
+    Command {
+        name: "ablative",
+        argument: PERSON,
+        description: "ablative prefix {ta}",
+    },
+    Command {
+        name: "terminative",
+        argument: PERSON,
+        description: "terminative prefix {ši}",
+    },
+    Command {
+        name: "locative",
+        argument: Some("in|on|in_with_initial_person|on_with_initial_person|oblique_second_sing"),
+        description: "locative prefix, the initial person is given with initial-person",
+    },
+    Command {
+        name: "final-person-prefix",
+        argument: PERSON,
+        description: "final person-prefix",
+    },
+    Command {
+        name: "final-person-suffix",
+        argument: PERSON,
+        description: "person-suffix",
+    },
+    Command {
+        name: "subordinator",
+        argument: None,
+        description: "nominalizing suffix {ʔa}",
+    },
+];
+
+pub fn find_command(name: &str) -> Result<&'static Command, ConjugationError> {
+    COMMANDS.iter().find(|command| command.name == name).ok_or(
+        ConjugationError::InvalidSpecification(format!("unknown command '{}'", name)),
+    )
+}
+
+// Calls the setter of the command, the persons are written 1sg, 2sg, 3sg.h, 3sg.nh...
+pub fn apply_command(
+    verb: &mut FiniteVerbalForm,
+    name: &str,
+    argument: Option<&str>,
+) -> Result<(), ConjugationError> {
+    let command = find_command(name)?;
+    let argument = match (command.argument, argument) {
+        (Some(_), Some(argument)) => argument,
+        (Some(expected), None) => {
+            return Err(ConjugationError::InvalidSpecification(format!(
+                "'{}' needs a {}",
+                name, expected
+            )))
+        }
+        (None, Some(argument)) => {
+            return Err(ConjugationError::InvalidSpecification(format!(
+                "'{}' does not take '{}'",
+                name, argument
+            )))
+        }
+        (None, None) => "",
+    };
+    let person = || argument.parse::<Person>();
+
+    match command.name {
+        "perfective" => {
+            verb.is_perfective();
+        }
+        "imperfective" => {
+            verb.is_imperfective(None);
+        }
+        "reduplicate" => {
+            verb.is_imperfective(Some(IpfvStem::Reduplicate));
+        }
+        "ed" => {
+            verb.is_imperfective(Some(IpfvStem::EdMarker));
+        }
+        "imperfective-stem" => {
+            verb.is_imperfective(Some(IpfvStem::Other(argument.to_string())));
+        }
+        "transitive" => {
+            verb.is_transitive();
+        }
+        "intransitive" => {
+            verb.is_intransitive();
+        }
+        "subject" => {
+            verb.set_subject(person()?);
+        }
+        "object" => {
+            verb.set_object(person()?)?;
+        }
+        "oblique" => {
+            verb.set_oblique_object(Some(person()?));
+        }
+        "negative" => {
+            verb.set_negative();
+        }
+        "modal" => {
+            verb.set_modal();
+        }
+        "preformative" => {
+            verb.set_preformative(Some(argument.parse::<Preformative>()?));
+        }
+        "coordinator" => {
+            verb.set_coordinator(Some(Coordinator));
+        }
+        "ventive" => {
+            verb.set_ventive();
+        }
+        "middle" => {
+            verb.set_middle_prefix(Some(MiddlePrefix));
+        }
+        "initial-person" => {
+            verb.set_initial_person_prefix(Some(person()?));
+        }
+        "dative" => {
+            verb.set_indirect_object(person()?);
+        }
+        "comitative" => {
+            verb.set_comitative(Some(person()?));
+        }
+        "ablative" => {
+            verb.set_ablative(Some(person()?));
+        }
+        "terminative" => {
+            verb.set_terminative(Some(person()?));
+        }
+        "locative" => match argument.parse::<LocativePrefix>()? {
+            LocativePrefix::InWithoutInitialPerson => {
+                verb.set_locative_in(None);
+            }
+            LocativePrefix::OnWithoutInitialPerson => {
+                verb.set_locative_on(None);
+            }
+            LocativePrefix::ObliqueSecondSing => {
+                verb.set_oblique_object(Some(Person::SecondSing));
+            }
+            locative => verb.slot_10 = Some(locative),
+        },
+        "final-person-prefix" => {
+            verb.set_final_person_prefix(Some(person()?));
+        }
+        "final-person-suffix" => {
+            verb.set_final_person_suffix(Some(person()?));
+        }
+        "subordinator" => {
+            verb.set_subordinator(true);
+        }
+        name => {
+            return Err(ConjugationError::InvalidSpecification(format!(
+                "the command '{}' is not handled",
+                name
+            )))
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Options {
+    pub stem: String,
+    pub from_lexicon: bool,
+    pub segmented: bool,
+    pub gloss: bool,
+    pub help: bool,
+    pub interactive: bool,
+    pub commands: Vec<(String, Option<String>)>,
+}
+
+impl Options {
+    // the commands are applied once the stem is known, so that the options can come in any order
+    pub fn verb(&self) -> Result<FiniteVerbalForm, ConjugationError> {
+        let mut verb = match self.from_lexicon {
+            true => FiniteVerbalForm::from_lexeme(&self.stem)?,
+            false => FiniteVerbalForm::from_stem(self.stem.clone()),
+        };
+        for (name, argument) in self.commands.iter() {
+            apply_command(&mut verb, name, argument.as_deref())?;
+        }
+        if matches!(
+            verb.slot_10,
+            Some(LocativePrefix::InWithInitialPerson | LocativePrefix::OnWithInitialPerson)
+        ) && verb.slot_6.is_none()
+        {
+            return Err(ConjugationError::InvalidSpecification(
+                "the locative with an initial person needs --initial-person".to_string(),
+            ));
+        }
+        Ok(verb)
+    }
+}
+
+// e.g. ["zu", "--perfective", "--transitive", "--subject", "3sg.h", "--object", "3sg.nh"]
+pub fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Options, ConjugationError> {
+    let mut options = Options::default();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--lexicon" => options.from_lexicon = true,
+            "--segmented" | "-s" => options.segmented = true,
+            "--gloss" | "-g" => options.gloss = true,
+            "--help" | "-h" => options.help = true,
+            "--interactive" | "-i" => options.interactive = true,
+            _ => match arg.strip_prefix("--") {
+                Some(name) => {
+                    let argument = match find_command(name)?.argument {
+                        Some(_) => Some(args.next().ok_or_else(|| {
+                            ConjugationError::InvalidSpecification(format!(
+                                "'--{}' needs a value",
+                                name
+                            ))
+                        })?),
+                        None => None,
+                    };
+                    options.commands.push((name.to_string(), argument));
+                }
+                None if options.stem.is_empty() => options.stem = arg,
+                None => {
+                    return Err(ConjugationError::InvalidSpecification(format!(
+                        "unexpected argument '{}'",
+                        arg
+                    )))
+                }
+            },
+        }
+    }
+    Ok(options)
+}
+
+pub fn usage() -> String {
+    let mut usage = String::from(
+        "Usage: sumerian-conjugate STEM [OPTIONS]\n\n\
+         Options:\n  \
+         --lexicon  take the stems and the transitivity from the lexicon\n  \
+         -s, --segmented  also print the segmented form\n  \
+         -g, --gloss  also print the gloss\n  \
+         -i, --interactive  build the verb step by step, the stem is then optional\n  \
+         -h, --help  print this message\n",
+    );
+    for command in COMMANDS.iter() {
+        let argument = command
+            .argument
+            .map(|argument| format!(" {}", argument))
+            .unwrap_or_default();
+        usage.push_str(&format!(
+            "  --{}{}  {}\n",
+            command.name, argument, command.description
+        ));
+    }
+    usage.push_str("\nPERSON is one of 1sg, 2sg, 3sg.h, 3sg.nh, 1pl, 2pl, 3pl.h, 3pl.nh\n");
+    usage
+}
@@ -0,0 +1,71 @@
+use crate::MarkerName;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConjugationError {
+    // the verbal stem (slot 12) is empty
+    EmptyStem,
+    // an object was set on a verb built as intransitive
+    ObjectOnIntransitive,
+    // the requested slots cannot be filled together
+    UnsupportedCombination {
+        slots: Vec<MarkerName>,
+        reason: String,
+    },
+    // the stem is not present in the lexicon
+    UnknownLexeme(String),
+    // an object was set on a verb marked as intransitive in the lexicon
+    IntransitiveLexeme(String),
+    // a stem is defined by two entries of the lexicon
+    DuplicateLexeme(String),
+    // a stem of the lexicon is empty or contains other characters than letters
+    MalformedStem(String),
+    // the lexicon could not be read or parsed
+    InvalidLexicon(String),
+    // a verb specification could not be read
+    InvalidSpecification(String),
+    // the result of a conjugation could not be written
+    InvalidResult(String),
+}
+
+impl fmt::Display for ConjugationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConjugationError::EmptyStem => write!(f, "Stem cannot be empty"),
+            ConjugationError::ObjectOnIntransitive => {
+                write!(f, "Cannot set object for intransitive verb")
+            }
+            ConjugationError::UnsupportedCombination { slots, reason } => {
+                let slots = slots
+                    .iter()
+                    .map(|slot| format!("{:?}", slot))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                write!(f, "Unsupported combination of {}: {}", slots, reason)
+            }
+            ConjugationError::UnknownLexeme(stem) => {
+                write!(f, "Verb '{}' not found in lexicon", stem)
+            }
+            ConjugationError::IntransitiveLexeme(stem) => {
+                write!(f, "Verb '{}' is intransitive in the lexicon", stem)
+            }
+            ConjugationError::DuplicateLexeme(stem) => {
+                write!(
+                    f,
+                    "Verb '{}' is defined more than once in the lexicon",
+                    stem
+                )
+            }
+            ConjugationError::MalformedStem(stem) => write!(f, "Malformed stem '{}'", stem),
+            ConjugationError::InvalidLexicon(reason) => write!(f, "Invalid lexicon: {}", reason),
+            ConjugationError::InvalidSpecification(reason) => {
+                write!(f, "Invalid verb specification: {}", reason)
+            }
+            ConjugationError::InvalidResult(reason) => {
+                write!(f, "Invalid conjugation result: {}", reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConjugationError {}
@@ -0,0 +1,284 @@
+use crate::{
+    ConjugatedForm, ConjugationError, Coordinator, DimensionalPrefixes, FiniteVerbalForm, IpfvStem,
+    LocativePrefix, MarkerName, MiddlePrefix, Person, Preformative,
+};
+use serde::{Deserialize, Serialize};
+
+/*
+    Conjugation of a verb described in JSON, e.g.
+    { "stem": "zu", "perfective": true, "transitive": true,
+      "subject": "3sg.h", "object": "3sg.nh", "ventive": true }
+    The persons are written 1sg, 2sg, 3sg.h, 3sg.nh, 1pl, 2pl, 3pl.h, 3pl.nh.
+    The result always is a JSON object with the surface form, its segmentation
+    and the errors met while reading the specification or printing the verb.
+*/
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct VerbSpecification {
+    pub stem: String,
+    // the stems and the transitivity are then taken from the lexicon
+    pub from_lexicon: bool,
+    pub perfective: bool,
+    pub imperfective_stem: Option<ImperfectiveStemSpecification>,
+    pub transitive: Option<bool>,
+    pub subject: Option<String>,
+    pub object: Option<String>,
+    pub oblique_object: Option<String>,
+    pub dimensional_prefixes: Option<DimensionalPrefixesSpecification>,
+    pub negative: bool,
+    pub modal: bool,
+    pub preformative: Option<String>,
+    pub coordinator: bool,
+    pub ventive: bool,
+    pub middle: bool,
+    pub subordinator: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImperfectiveStemSpecification {
+    Reduplicate,
+    EdMarker,
+    Other(String),
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DimensionalPrefixesSpecification {
+    pub indirect_object: Option<String>,
+    pub comitative: bool,
+    pub ablative: bool,
+    pub terminative: bool,
+    // "in", "on", "in_with_initial_person", "on_with_initial_person" or "oblique_second_sing"
+    pub locative: Option<String>,
+    pub initial_person: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ConjugationResult {
+    pub surface: Option<String>,
+    pub hyphenated: Option<String>,
+    pub gloss: Option<String>,
+    pub segmentation: Vec<MorphResult>,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MorphResult {
+    pub slot: String,
+    pub morpheme: String,
+    pub surface: String,
+    pub gloss: String,
+}
+
+// the name of the slot in the result, which does not depend on the Rust names
+fn slot_name(slot: &MarkerName) -> &'static str {
+    match slot {
+        MarkerName::FirstPrefix => "first_prefix",
+        MarkerName::Preformative => "preformative",
+        MarkerName::Coordinator => "coordinator",
+        MarkerName::Ventive => "ventive",
+        MarkerName::MiddlePrefix => "middle_prefix",
+        MarkerName::InitialPronominalPrefix => "initial_person_prefix",
+        MarkerName::DativePrefix => "indirect_object_prefix",
+        MarkerName::ComitativePrefix => "comitative_prefix",
+        MarkerName::AdverbialPrefix => "adverbial_prefix",
+        MarkerName::LocativePrefix => "locative_prefix",
+        MarkerName::FinalPersonPrefix => "final_person_prefix",
+        MarkerName::Stem => "stem",
+        MarkerName::EdMarker => "ed_marker",
+        MarkerName::FinalPersonSuffix => "final_person_suffix",
+        MarkerName::Subordinator => "subordinator",
+    }
+}
+
+// keeps the value, or the error with the others met in the specification
+fn collect<T>(
+    result: Result<T, ConjugationError>,
+    errors: &mut Vec<ConjugationError>,
+) -> Option<T> {
+    match result {
+        Ok(value) => Some(value),
+        Err(error) => {
+            errors.push(error);
+            None
+        }
+    }
+}
+
+fn person(abbreviation: &Option<String>) -> Result<Option<Person>, ConjugationError> {
+    abbreviation
+        .as_deref()
+        .map(str::parse::<Person>)
+        .transpose()
+}
+
+impl DimensionalPrefixesSpecification {
+    pub fn to_prefixes(&self) -> Result<DimensionalPrefixes, Vec<ConjugationError>> {
+        let mut errors = Vec::new();
+        let locative = collect(
+            self.locative
+                .as_deref()
+                .map(str::parse::<LocativePrefix>)
+                .transpose(),
+            &mut errors,
+        )
+        .flatten();
+        let initial_person = collect(person(&self.initial_person), &mut errors).flatten();
+        if (self.comitative || self.ablative || self.terminative) && self.initial_person.is_none() {
+            errors.push(ConjugationError::InvalidSpecification(
+                "the comitative, ablative and terminative prefixes need an initial person"
+                    .to_string(),
+            ));
+        }
+        if matches!(
+            locative,
+            Some(LocativePrefix::InWithInitialPerson | LocativePrefix::OnWithInitialPerson)
+        ) && self.initial_person.is_none()
+        {
+            errors.push(ConjugationError::InvalidSpecification(
+                "the locative with an initial person needs an initial person".to_string(),
+            ));
+        }
+        let indirect_object = collect(person(&self.indirect_object), &mut errors).flatten();
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        Ok(DimensionalPrefixes {
+            indirect_object,
+            comitative: self.comitative,
+            locative,
+            ablative: self.ablative,
+            terminative: self.terminative,
+            initial_person,
+        })
+    }
+}
+
+impl VerbSpecification {
+    // every error of the specification is returned, not only the first one
+    pub fn to_verb(&self) -> Result<FiniteVerbalForm, Vec<ConjugationError>> {
+        let mut errors = Vec::new();
+        let mut verb = match self.from_lexicon {
+            true => collect(FiniteVerbalForm::from_lexeme(&self.stem), &mut errors)
+                .unwrap_or_else(|| FiniteVerbalForm::from_stem(self.stem.clone())),
+            false => FiniteVerbalForm::from_stem(self.stem.clone()),
+        };
+        match self.transitive {
+            Some(true) => {
+                verb.is_transitive();
+            }
+            Some(false) => {
+                verb.is_intransitive();
+            }
+            None => (),
+        }
+        if self.perfective {
+            verb.is_perfective();
+        } else {
+            verb.is_imperfective(self.imperfective_stem.clone().map(|stem| match stem {
+                ImperfectiveStemSpecification::Reduplicate => IpfvStem::Reduplicate,
+                ImperfectiveStemSpecification::EdMarker => IpfvStem::EdMarker,
+                ImperfectiveStemSpecification::Other(stem) => IpfvStem::Other(stem),
+            }));
+        }
+
+        if let Some(subject) = collect(person(&self.subject), &mut errors).flatten() {
+            verb.set_subject(subject);
+        }
+        if let Some(object) = collect(person(&self.object), &mut errors).flatten() {
+            collect(verb.set_object(object).map(|_| ()), &mut errors);
+        }
+        if let Some(prefixes) = &self.dimensional_prefixes {
+            match prefixes.to_prefixes() {
+                Ok(prefixes) => {
+                    collect(
+                        verb.set_dimensional_prefixes(&prefixes).map(|_| ()),
+                        &mut errors,
+                    );
+                }
+                Err(prefix_errors) => errors.extend(prefix_errors),
+            }
+        }
+        if let Some(oblique_object) = collect(person(&self.oblique_object), &mut errors).flatten() {
+            verb.set_oblique_object(Some(oblique_object));
+        }
+
+        // the negative and the modal prefixes both fill the slot 1
+        match (self.negative, self.modal) {
+            (true, true) => errors.push(ConjugationError::InvalidSpecification(
+                "a verb cannot be both negative and modal".to_string(),
+            )),
+            (true, false) => {
+                verb.set_negative();
+            }
+            (false, true) => {
+                verb.set_modal();
+            }
+            (false, false) => (),
+        }
+        if let Some(preformative) = &self.preformative {
+            if let Some(preformative) = collect(preformative.parse::<Preformative>(), &mut errors) {
+                verb.set_preformative(Some(preformative));
+            }
+        }
+        verb.set_coordinator(self.coordinator.then_some(Coordinator))
+            .set_middle_prefix(self.middle.then_some(MiddlePrefix))
+            .set_subordinator(self.subordinator);
+        if self.ventive {
+            verb.set_ventive();
+        }
+
+        match errors.is_empty() {
+            true => Ok(verb),
+            false => Err(errors),
+        }
+    }
+}
+
+impl From<Result<ConjugatedForm, ConjugationError>> for ConjugationResult {
+    fn from(form: Result<ConjugatedForm, ConjugationError>) -> Self {
+        match form {
+            Ok(form) => ConjugationResult {
+                surface: Some(form.surface()),
+                hyphenated: Some(form.hyphenated()),
+                gloss: Some(form.gloss()),
+                segmentation: form
+                    .morphs
+                    .into_iter()
+                    .map(|morph| MorphResult {
+                        slot: slot_name(&morph.slot).to_string(),
+                        morpheme: morph.morpheme,
+                        surface: morph.surface,
+                        gloss: morph.gloss,
+                    })
+                    .collect(),
+                errors: Vec::new(),
+            },
+            Err(error) => ConjugationResult {
+                errors: vec![error.to_string()],
+                ..ConjugationResult::default()
+            },
+        }
+    }
+}
+
+pub fn conjugate(specification: &str) -> ConjugationResult {
+    let verb = serde_json::from_str::<VerbSpecification>(specification)
+        .map_err(|err| vec![ConjugationError::InvalidSpecification(err.to_string())])
+        .and_then(|specification| specification.to_verb());
+    match verb {
+        Ok(mut verb) => verb.print_segmented().into(),
+        Err(errors) => ConjugationResult {
+            errors: errors.iter().map(ConjugationError::to_string).collect(),
+            ..ConjugationResult::default()
+        },
+    }
+}
+
+// e.g. {"surface":"munzu","hyphenated":"mu-n-zu","gloss":"VEN-3SG.H.A-know","segmentation":[...],"errors":[]}
+pub fn conjugate_json(specification: &str) -> Result<String, ConjugationError> {
+    serde_json::to_string(&conjugate(specification))
+        .map_err(|err| ConjugationError::InvalidResult(err.to_string()))
+}
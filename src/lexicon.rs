@@ -1,24 +1,82 @@
+use crate::utils::is_vowel;
+use crate::{ConjugationError, FiniteVerbalForm};
+#[cfg(feature = "loader")]
+use std::path::Path;
+use std::sync::OnceLock;
+
+// the built-in entries borrow their strings, they are read back as LexiconEntry
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct VerbAspect<'a> {
     pub perf: &'a str,
     pub imperf: &'a str,
 }
 
+// 12.4.3 formation of the imperfective (marû) stem
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(any(feature = "loader", feature = "serde"), derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(
+    any(feature = "loader", feature = "serde"),
+    serde(rename_all = "snake_case")
+)]
+pub enum MaruClass {
+    PartialReduplication, // tuku => tuktuku
+    FullReduplication,    // zig => zig-zig
+    Alternating,          // another form of the stem, given in the lexicon
+    Suppletive,           // another stem, given in the lexicon
+    EAddition,            // the stem followed by /e/
+}
+impl MaruClass {
+    // imperfective stem derived from the perfective one, if the class allows it
+    pub fn derive(&self, stem: &str) -> Option<String> {
+        match self {
+            MaruClass::PartialReduplication => {
+                // the stem up to the consonant following its first vowel is repeated,
+                // a stem without such a consonant cannot be partially reduplicated
+                let letters: Vec<char> = stem.chars().collect();
+                let first_vowel = letters.iter().position(|letter| is_vowel(*letter))?;
+                // the accents written as combining characters belong to the vowel
+                let consonant = first_vowel
+                    + 1
+                    + letters[first_vowel + 1..]
+                        .iter()
+                        .take_while(|letter| ('\u{300}'..='\u{36f}').contains(*letter))
+                        .count();
+                if !letters
+                    .get(consonant)
+                    .is_some_and(|letter| letter.is_alphabetic() && !is_vowel(*letter))
+                {
+                    return None;
+                }
+                let repeated: String = letters[..=consonant].iter().collect();
+                Some(format!("{}{}", repeated, stem))
+            }
+            MaruClass::FullReduplication => Some(format!("{}-{}", stem, stem)),
+            MaruClass::EAddition => Some(format!("{}e", stem)),
+            MaruClass::Alternating | MaruClass::Suppletive => None,
+        }
+    }
+}
+
 // 12.4.2
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct VerbForm<'a> {
     pub stem: &'a str,
     pub meaning: &'a str,
     pub transitive: bool,
+    pub maru: Option<MaruClass>, // None when the imperfective stem is the perfective one
     pub sing: VerbAspect<'a>,
     pub plur: VerbAspect<'a>,
 }
 
-pub const VERBFORMS: [VerbForm; 10] = [
+pub const VERBFORMS: [VerbForm; 11] = [
     VerbForm {
         stem: "dug",
         meaning: "to do",
         transitive: true,
+        maru: None,
         sing: VerbAspect {
             perf: "dug",
             imperf: "dug",
@@ -32,6 +90,7 @@ pub const VERBFORMS: [VerbForm; 10] = [
         stem: "ĝen",
         meaning: "to go",
         transitive: false,
+        maru: Some(MaruClass::Suppletive),
         sing: VerbAspect {
             perf: "ĝen",
             imperf: "du",
@@ -45,6 +104,7 @@ pub const VERBFORMS: [VerbForm; 10] = [
         stem: "gi",
         meaning: "to turn, to send",
         transitive: true,
+        maru: Some(MaruClass::FullReduplication),
         sing: VerbAspect {
             perf: "gi",
             imperf: "gi-gi",
@@ -58,6 +118,7 @@ pub const VERBFORMS: [VerbForm; 10] = [
         stem: "gub",
         meaning: "to stand",
         transitive: false,
+        maru: None,
         sing: VerbAspect {
             perf: "gub",
             imperf: "gub",
@@ -71,6 +132,7 @@ pub const VERBFORMS: [VerbForm; 10] = [
         stem: "hulu",
         meaning: "to be bad",
         transitive: false,
+        maru: Some(MaruClass::PartialReduplication),
         sing: VerbAspect {
             perf: "hulu",
             imperf: "hulhulu",
@@ -84,6 +146,7 @@ pub const VERBFORMS: [VerbForm; 10] = [
         stem: "re",
         meaning: "to bring",
         transitive: true,
+        maru: None,
         sing: VerbAspect {
             perf: "re",
             imperf: "re",
@@ -97,6 +160,7 @@ pub const VERBFORMS: [VerbForm; 10] = [
         stem: "til",
         meaning: "to live",
         transitive: false,
+        maru: None,
         sing: VerbAspect {
             perf: "til",
             imperf: "til",
@@ -110,6 +174,7 @@ pub const VERBFORMS: [VerbForm; 10] = [
         stem: "tuku",
         meaning: "to have",
         transitive: true,
+        maru: Some(MaruClass::PartialReduplication),
         sing: VerbAspect {
             perf: "tuku",
             imperf: "tuktuku",
@@ -123,6 +188,7 @@ pub const VERBFORMS: [VerbForm; 10] = [
         stem: "tuš",
         meaning: "to sit",
         transitive: false,
+        maru: Some(MaruClass::Suppletive),
         sing: VerbAspect {
             perf: "tuš",
             imperf: "dur",
@@ -136,19 +202,35 @@ pub const VERBFORMS: [VerbForm; 10] = [
         stem: "zig",
         meaning: "to rise, to raise",
         transitive: false,
+        maru: Some(MaruClass::FullReduplication),
         sing: VerbAspect {
             perf: "zig",
-            imperf: "zigzig",
+            imperf: "zig-zig",
         },
         plur: VerbAspect {
             perf: "zig",
-            imperf: "zigzig",
+            imperf: "zig-zig",
+        },
+    },
+    VerbForm {
+        stem: "zu",
+        meaning: "to know",
+        transitive: true,
+        maru: None,
+        sing: VerbAspect {
+            perf: "zu",
+            imperf: "zu",
+        },
+        plur: VerbAspect {
+            perf: "zu",
+            imperf: "zu",
         },
     },
     // VerbForm {
     //     stem: "",
     //     meaning: "to ",
     //     transitive: ,
+    //     maru: None,
     //     sing: VerbAspect {
     //         perf: "",
     //         imperf: "",
@@ -168,11 +250,310 @@ pub fn available_verb_stems() -> Vec<String> {
     stems
 }
 
-pub fn find_verb(stem: &str) -> Result<&'static VerbForm<'static>, String> {
+/*
+    A meaning matches the query, ignoring the case and a leading "to":
+    0. exactly ("Go" matches "to go")
+    1. word for word ("raise" matches "to raise up")
+    2. as a substring ("hav" matches "to have")
+    The rank of an entry is that of its best meaning, then the position of this meaning.
+*/
+fn meaning_rank<'a>(
+    meanings: impl Iterator<Item = &'a str>,
+    query: &str,
+) -> Option<(usize, usize)> {
+    let query = normalize_meaning(query);
+    if query.is_empty() {
+        return None;
+    }
+    let query_words: Vec<&str> = query.split_whitespace().collect();
+    meanings
+        .enumerate()
+        .filter_map(|(position, meaning)| {
+            let meaning = normalize_meaning(meaning);
+            let words: Vec<&str> = meaning.split_whitespace().collect();
+            let rank = if meaning == query {
+                0
+            } else if words
+                .windows(query_words.len())
+                .any(|window| window == query_words.as_slice())
+            {
+                1
+            } else if meaning.contains(&query) {
+                2
+            } else {
+                return None;
+            };
+            Some((rank, position))
+        })
+        .min()
+}
+
+fn normalize_meaning(meaning: &str) -> String {
+    let meaning = meaning.trim().to_lowercase();
+    match meaning.strip_prefix("to ") {
+        Some(meaning) => meaning.trim().to_string(),
+        None => meaning,
+    }
+}
+
+// looks in the built-in verbs only, a loaded lexicon has its own Lexicon::find_verb
+pub fn find_verb(stem: &str) -> Result<&'static VerbForm<'static>, ConjugationError> {
     for verb in VERBFORMS.iter() {
         if verb.stem == stem {
             return Ok(verb);
         }
     }
-    Err(format!("Verb '{}' not found in lexicon", stem))
+    Err(ConjugationError::UnknownLexeme(stem.to_string()))
+}
+
+/*
+    Entries of the lexicon owning their strings, built from VERBFORMS
+    or read from a JSON or TOML file with the "loader" feature.
+*/
+
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(any(feature = "loader", feature = "serde"), derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AspectStems {
+    #[cfg_attr(any(feature = "loader", feature = "serde"), serde(default))]
+    pub perf: String,
+    #[cfg_attr(any(feature = "loader", feature = "serde"), serde(default))]
+    pub imperf: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(any(feature = "loader", feature = "serde"), derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LexiconEntry {
+    pub stem: String,
+    pub meanings: Vec<String>,
+    pub transitive: bool,
+    // the missing stems default to the stem, the plural stems to the singular ones
+    #[cfg_attr(any(feature = "loader", feature = "serde"), serde(default))]
+    pub sing: AspectStems,
+    #[cfg_attr(any(feature = "loader", feature = "serde"), serde(default))]
+    pub plur: AspectStems,
+    #[cfg_attr(any(feature = "loader", feature = "serde"), serde(default))]
+    pub maru: Option<MaruClass>,
+    // sections of the grammar or texts attesting the verb
+    #[cfg_attr(any(feature = "loader", feature = "serde"), serde(default))]
+    pub citations: Vec<String>,
+}
+
+impl From<&VerbForm<'_>> for LexiconEntry {
+    fn from(verb: &VerbForm<'_>) -> Self {
+        LexiconEntry {
+            stem: verb.stem.to_string(),
+            meanings: verb
+                .meaning
+                .split(',')
+                .map(|meaning| meaning.trim().to_string())
+                .collect(),
+            transitive: verb.transitive,
+            maru: verb.maru,
+            sing: AspectStems {
+                perf: verb.sing.perf.to_string(),
+                imperf: verb.sing.imperf.to_string(),
+            },
+            plur: AspectStems {
+                perf: verb.plur.perf.to_string(),
+                imperf: verb.plur.imperf.to_string(),
+            },
+            citations: Vec::new(),
+        }
+    }
+}
+
+impl LexiconEntry {
+    // first meaning of the entry, e.g. "to be bad" => "be.bad"
+    pub fn gloss(&self) -> String {
+        let meaning = self
+            .meanings
+            .first()
+            .map(|meaning| meaning.trim())
+            .unwrap_or("");
+        meaning
+            .strip_prefix("to ")
+            .unwrap_or(meaning)
+            .replace(' ', ".")
+    }
+
+    // 12.4.2 the plural stems are used when the absolutive participant
+    // (the subject of an intransitive verb, the object of a transitive verb) is plural
+    pub fn stem_for(&self, is_perfective: bool, plural_absolutive: bool) -> &str {
+        let aspects = match plural_absolutive {
+            true => &self.plur,
+            false => &self.sing,
+        };
+        match is_perfective {
+            true => &aspects.perf,
+            false => &aspects.imperf,
+        }
+    }
+
+    pub fn forms(&self) -> [&str; 5] {
+        [
+            &self.stem,
+            &self.sing.perf,
+            &self.sing.imperf,
+            &self.plur.perf,
+            &self.plur.imperf,
+        ]
+    }
+
+    // Fills the missing stems, the imperfective ones being derived from the marû class
+    fn complete(&mut self) -> Result<(), ConjugationError> {
+        let maru = self.maru;
+        let imperfective = |perf: &str| match maru {
+            Some(class) => class.derive(perf),
+            None => Some(perf.to_string()),
+        };
+        if self.sing.perf.is_empty() {
+            self.sing.perf = self.stem.clone();
+        }
+        if self.sing.imperf.is_empty() {
+            self.sing.imperf = imperfective(&self.sing.perf).ok_or_else(|| {
+                ConjugationError::InvalidLexicon(format!(
+                    "the imperfective stem of '{}' must be given",
+                    self.stem
+                ))
+            })?;
+        }
+        if self.plur.perf.is_empty() {
+            self.plur.perf = self.sing.perf.clone();
+        }
+        if self.plur.imperf.is_empty() {
+            self.plur.imperf = match self.plur.perf == self.sing.perf {
+                true => self.sing.imperf.clone(),
+                false => imperfective(&self.plur.perf).unwrap_or(self.sing.imperf.clone()),
+            };
+        }
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), ConjugationError> {
+        match self.forms().into_iter().find(|form| !is_well_formed(form)) {
+            Some(form) => Err(ConjugationError::MalformedStem(form.to_string())),
+            None => Ok(()),
+        }
+    }
+}
+
+// a stem is made of lowercase letters, possibly joined by hyphens ("gi-gi")
+fn is_well_formed(stem: &str) -> bool {
+    !stem.is_empty()
+        && stem.split('-').all(|part| {
+            !part.is_empty()
+                && part.chars().all(|letter| {
+                    letter == 'ʔ' || (letter.is_alphabetic() && !letter.is_uppercase())
+                })
+        })
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(any(feature = "loader", feature = "serde"), derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Lexicon {
+    pub verbs: Vec<LexiconEntry>,
+}
+
+impl Lexicon {
+    pub fn builtin() -> Self {
+        Lexicon {
+            verbs: VERBFORMS.iter().map(LexiconEntry::from).collect(),
+        }
+    }
+
+    // the built-in lexicon, built once for the verbs that are printed
+    pub(crate) fn builtin_ref() -> &'static Lexicon {
+        static BUILTIN: OnceLock<Lexicon> = OnceLock::new();
+        BUILTIN.get_or_init(Lexicon::builtin)
+    }
+
+    pub fn find_verb(&self, stem: &str) -> Result<&LexiconEntry, ConjugationError> {
+        self.verbs
+            .iter()
+            .find(|verb| verb.stem == stem)
+            .ok_or(ConjugationError::UnknownLexeme(stem.to_string()))
+    }
+
+    // the verb of the entry, with its stems and its transitivity
+    pub fn verb(&self, stem: &str) -> Result<FiniteVerbalForm, ConjugationError> {
+        Ok(FiniteVerbalForm::from_lexicon_entry(self.find_verb(stem)?))
+    }
+
+    // looks for the entry any of whose stems is the given form
+    pub fn find_verb_by_form(&self, form: &str) -> Option<&LexiconEntry> {
+        self.verbs.iter().find(|verb| verb.forms().contains(&form))
+    }
+
+    // Entries one of whose meanings matches the English query, best matches first
+    pub fn find_verbs_by_meaning(&self, query: &str) -> Vec<&LexiconEntry> {
+        let mut matches: Vec<((usize, usize), &LexiconEntry)> = self
+            .verbs
+            .iter()
+            .filter_map(|verb| {
+                meaning_rank(verb.meanings.iter().map(String::as_str), query)
+                    .map(|rank| (rank, verb))
+            })
+            .collect();
+        matches.sort_by_key(|(rank, _)| *rank);
+        matches.into_iter().map(|(_, verb)| verb).collect()
+    }
+
+    // Adds the entries of the other lexicon, a stem cannot be defined twice.
+    // The lexicon is left unchanged if one of the entries is rejected
+    pub fn merge(&mut self, other: Lexicon) -> Result<&mut Self, ConjugationError> {
+        let mut merged = self.clone();
+        for mut verb in other.verbs {
+            verb.complete()?;
+            verb.validate()?;
+            if merged.find_verb(&verb.stem).is_ok() {
+                return Err(ConjugationError::DuplicateLexeme(verb.stem));
+            }
+            merged.verbs.push(verb);
+        }
+        *self = merged;
+        Ok(self)
+    }
+
+    // e.g. { "verbs": [{ "stem": "ĝen", "meanings": ["to go"], "transitive": false }] }
+    #[cfg(feature = "loader")]
+    pub fn from_json(source: &str) -> Result<Self, ConjugationError> {
+        let lexicon: Lexicon = serde_json::from_str(source)
+            .map_err(|err| ConjugationError::InvalidLexicon(err.to_string()))?;
+        Lexicon::default().merge(lexicon).cloned()
+    }
+
+    // e.g. [[verbs]] stem = "ĝen" meanings = ["to go"] transitive = false
+    #[cfg(feature = "loader")]
+    pub fn from_toml(source: &str) -> Result<Self, ConjugationError> {
+        let lexicon: Lexicon = toml::from_str(source)
+            .map_err(|err| ConjugationError::InvalidLexicon(err.to_string()))?;
+        Lexicon::default().merge(lexicon).cloned()
+    }
+
+    // the format is chosen from the extension of the file, .json or .toml
+    #[cfg(feature = "loader")]
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ConjugationError> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path)
+            .map_err(|err| ConjugationError::InvalidLexicon(err.to_string()))?;
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => Lexicon::from_json(&source),
+            Some("toml") => Lexicon::from_toml(&source),
+            _ => Err(ConjugationError::InvalidLexicon(format!(
+                "unknown lexicon format: {}",
+                path.display()
+            ))),
+        }
+    }
+
+    // the built-in entries followed by the entries of the file
+    #[cfg(feature = "loader")]
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ConjugationError> {
+        let mut lexicon = Lexicon::builtin();
+        lexicon.merge(Lexicon::from_file(path)?)?;
+        Ok(lexicon)
+    }
 }
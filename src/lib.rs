@@ -1,8 +1,22 @@
-mod lexicon;
+pub mod analyzer;
+#[cfg(feature = "cli")]
+pub mod cli;
+mod errors;
+#[cfg(feature = "json")]
+pub mod json;
+pub mod lexicon;
+pub mod paradigm;
+pub mod phonology;
+#[cfg(feature = "serde")]
+mod presence;
+#[cfg(feature = "cli")]
+pub mod repl;
+#[allow(clippy::module_inception)]
 mod tests;
 mod utils;
-use crate::lexicon::{find_verb, VerbAspect, VerbForm};
-use crate::utils::ends_with_vowel;
+pub mod wasm;
+pub use crate::errors::ConjugationError;
+use wasm_bindgen::prelude::wasm_bindgen;
 
 /*
     Slot 1 Modal prefix (ḫa), negative particle, prefix of anteriority, stem (in imperative forms)
@@ -26,16 +40,29 @@ use crate::utils::ends_with_vowel;
 */
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FiniteVerbalForm {
     pub is_perfective: bool,
     pub is_transitive: bool,
+    // arguments of the verb, resolved into slots 11 and 14 when the verb is printed
+    pub subject: Option<Person>,
+    pub object: Option<Person>,
+    pub oblique_object: Option<Person>,
+    // lexicon entry providing the suppletive stems, chosen when the verb is printed
+    pub lexeme: Option<lexicon::LexiconEntry>,
+    // formation of the imperfective stem, only applied to an imperfective verb when it is printed
+    pub imperfective_stem: Option<IpfvStem>,
     pub slot_1: Option<FirstPrefix>,
     pub slot_2: Option<Preformative>, // finite marker prefix
+    #[cfg_attr(feature = "serde", serde(with = "presence"))]
     pub slot_3: Option<Coordinator>,
+    #[cfg_attr(feature = "serde", serde(with = "presence"))]
     pub slot_4: Option<Ventive>,
+    #[cfg_attr(feature = "serde", serde(with = "presence"))]
     pub slot_5: Option<MiddlePrefix>,
     pub slot_6: Option<InitialPersonPrefix>,
     pub slot_7: Option<IndirectObjectPrefix>,
+    #[cfg_attr(feature = "serde", serde(with = "presence"))]
     pub slot_8: Option<ComitativePrefix>,
     pub slot_9: Option<AdverbialPrefix>,
     pub slot_10: Option<LocativePrefix>,
@@ -50,6 +77,11 @@ impl FiniteVerbalForm {
         FiniteVerbalForm {
             is_perfective,
             is_transitive,
+            subject: None,
+            object: None,
+            oblique_object: None,
+            lexeme: None,
+            imperfective_stem: None,
             slot_1: None,
             slot_2: None,
             slot_3: None,
@@ -72,6 +104,11 @@ impl FiniteVerbalForm {
         FiniteVerbalForm {
             is_perfective: false,
             is_transitive: false,
+            subject: None,
+            object: None,
+            oblique_object: None,
+            lexeme: None,
+            imperfective_stem: None,
             slot_1: None,
             slot_2: None,
             slot_3: None,
@@ -89,6 +126,17 @@ impl FiniteVerbalForm {
             slot_15: false,
         }
     }
+    // a verb of the built-in lexicon, see Lexicon::verb for a loaded lexicon
+    pub fn from_lexeme(stem: &str) -> Result<Self, ConjugationError> {
+        lexicon::Lexicon::builtin_ref().verb(stem)
+    }
+    pub fn from_lexicon_entry(lexeme: &lexicon::LexiconEntry) -> Self {
+        let mut verb = FiniteVerbalForm::from_stem(lexeme.stem.clone());
+        // the transitivity of the entry can still be changed with is_transitive/is_intransitive
+        verb.is_transitive = lexeme.transitive;
+        verb.lexeme = Some(lexeme.clone());
+        verb
+    }
     pub fn is_transitive(&mut self) -> &mut Self {
         self.is_transitive = true;
         self
@@ -103,19 +151,7 @@ impl FiniteVerbalForm {
     }
     pub fn is_imperfective(&mut self, impf_stem: Option<IpfvStem>) -> &mut Self {
         self.is_perfective = false;
-        if let Some(stem) = impf_stem {
-            match stem {
-                IpfvStem::Reduplicate => {
-                    self.slot_12 = format!("{}-{}", self.slot_12, self.slot_12);
-                }
-                IpfvStem::EdMarker => {
-                    self.slot_13 = Some("ed".to_string());
-                }
-                IpfvStem::Other(stem) => {
-                    self.slot_12 = stem;
-                }
-            }
-        }
+        self.imperfective_stem = impf_stem;
         self
     }
     pub fn set_negative(&mut self) -> &mut Self {
@@ -216,6 +252,47 @@ impl FiniteVerbalForm {
         self.slot_9 = adverbial_prefix;
         self
     }
+    // Replaces the slots 6 to 10 with the given dimensional prefixes
+    pub fn set_dimensional_prefixes(
+        &mut self,
+        prefixes: &DimensionalPrefixes,
+    ) -> Result<&mut Self, ConjugationError> {
+        if prefixes.ablative && prefixes.terminative {
+            return Err(ConjugationError::UnsupportedCombination {
+                slots: vec![MarkerName::AdverbialPrefix],
+                reason: "the ablative and the terminative prefixes fill the same slot".to_string(),
+            });
+        }
+        self.slot_7 = None;
+        if let Some(person) = prefixes.indirect_object.clone() {
+            self.set_indirect_object(person);
+        }
+        self.slot_8 = match prefixes.comitative {
+            true => Some(ComitativePrefix),
+            false => None,
+        };
+        self.slot_9 = match (prefixes.ablative, prefixes.terminative) {
+            (true, _) => Some(AdverbialPrefix::Ablative),
+            (false, true) => Some(AdverbialPrefix::Terminative),
+            (false, false) => None,
+        };
+        self.slot_10 = prefixes.locative.clone();
+        match self.uses_initial_person() {
+            true => self.set_initial_person_prefix(prefixes.initial_person.clone()),
+            false => self.set_initial_person_prefix(None),
+        };
+        Ok(self)
+    }
+    // the initial person-prefix is kept as long as one of the dimensional prefixes refers to it
+    pub(crate) fn uses_initial_person(&self) -> bool {
+        self.slot_7.is_some()
+            || self.slot_8.is_some()
+            || self.slot_9.is_some()
+            || matches!(
+                self.slot_10,
+                Some(LocativePrefix::InWithInitialPerson | LocativePrefix::OnWithInitialPerson)
+            )
+    }
     pub fn set_locative_in(&mut self, initial_person: Option<Person>) -> &mut Self {
         self.slot_10 = match initial_person {
             Some(_) => Some(LocativePrefix::InWithInitialPerson),
@@ -232,6 +309,21 @@ impl FiniteVerbalForm {
         // self.set_initial_person_prefix(initial_person);
         self
     }
+    pub fn set_oblique_object(&mut self, oblique_object: Option<Person>) -> &mut Self {
+        // 13.1 The oblique object (locative3) is expressed by the final person-prefix,
+        // except for the second person singular that has its own prefix {ri}
+        match oblique_object {
+            Some(Person::SecondSing) => self.slot_10 = Some(LocativePrefix::ObliqueSecondSing),
+            Some(person) => {
+                self.slot_10 = Some(LocativePrefix::ObliqueWithFinalPerson);
+                self.oblique_object = Some(person);
+                return self;
+            }
+            None => self.slot_10 = None,
+        }
+        self.oblique_object = None;
+        self
+    }
     pub fn set_final_person_prefix(&mut self, final_person_prefix: Option<Person>) -> &mut Self {
         match final_person_prefix {
             Some(prefix) => match prefix {
@@ -241,11 +333,11 @@ impl FiniteVerbalForm {
                 Person::ThirdSingNonHuman => {
                     self.slot_11 = Some(FinalPersonPrefix::ThirdSingNonHuman)
                 }
-                Person::FirstPlur => self.slot_11 = Some(FinalPersonPrefix::FirstSingHuman),
-                Person::SecondPlur => self.slot_11 = Some(FinalPersonPrefix::SecondSingHuman),
-                Person::ThirdPlurHuman => self.slot_11 = Some(FinalPersonPrefix::ThirdSingHuman),
+                Person::FirstPlur => self.slot_11 = Some(FinalPersonPrefix::FirstPlurHuman),
+                Person::SecondPlur => self.slot_11 = Some(FinalPersonPrefix::SecondPlurHuman),
+                Person::ThirdPlurHuman => self.slot_11 = Some(FinalPersonPrefix::ThirdPlurHuman),
                 Person::ThirdPlurNonHuman => {
-                    self.slot_11 = Some(FinalPersonPrefix::ThirdSingNonHuman)
+                    self.slot_11 = Some(FinalPersonPrefix::ThirdPlurNonHuman)
                 }
             },
             None => self.slot_11 = None,
@@ -281,129 +373,322 @@ impl FiniteVerbalForm {
         self
     }
     pub fn set_subject(&mut self, subject: Person) -> &mut Self {
-        if !self.is_transitive || (self.is_transitive && !self.is_perfective) {
-            self.slot_14 = match subject {
-                Person::FirstSing => Some(FinalPersonSuffix::FirstSingHuman),
-                Person::SecondSing => Some(FinalPersonSuffix::SecondSingHuman),
-                Person::ThirdSingHuman => Some(FinalPersonSuffix::ThirdSingHuman),
-                Person::ThirdSingNonHuman => Some(FinalPersonSuffix::ThirdSingNonHuman),
-                Person::FirstPlur => Some(FinalPersonSuffix::FirstPlurHuman),
-                Person::SecondPlur => Some(FinalPersonSuffix::SecondPlurHuman),
-                Person::ThirdPlurHuman => Some(FinalPersonSuffix::ThirdPlurHuman),
-                Person::ThirdPlurNonHuman => Some(FinalPersonSuffix::ThirdPlurNonHuman),
-            };
-        } else {
-            self.slot_11 = match subject {
-                Person::FirstSing => Some(FinalPersonPrefix::FirstSingHuman),
-                Person::SecondSing => Some(FinalPersonPrefix::SecondSingHuman),
-                Person::ThirdSingHuman => Some(FinalPersonPrefix::ThirdSingHuman),
-                Person::ThirdSingNonHuman => Some(FinalPersonPrefix::ThirdSingNonHuman),
-                _ => todo!("subject not implemented for plurals"),
-            };
-        }
+        self.subject = Some(subject);
         self
     }
-    pub fn set_object(&mut self, object: Person) -> Result<&mut Self, String> {
-        if self.is_transitive && self.is_perfective {
-            self.slot_14 = match object {
-                Person::FirstSing => Some(FinalPersonSuffix::FirstSingHuman),
-                Person::SecondSing => Some(FinalPersonSuffix::SecondSingHuman),
-                Person::ThirdSingHuman => Some(FinalPersonSuffix::ThirdSingHuman),
-                Person::ThirdSingNonHuman => Some(FinalPersonSuffix::ThirdSingNonHuman),
-                Person::FirstPlur => Some(FinalPersonSuffix::FirstSingHuman),
-                Person::SecondPlur => Some(FinalPersonSuffix::SecondSingHuman),
-                Person::ThirdPlurHuman => Some(FinalPersonSuffix::ThirdSingHuman),
-                Person::ThirdPlurNonHuman => Some(FinalPersonSuffix::ThirdSingNonHuman),
-            };
-        } else if self.is_transitive && !self.is_perfective {
-            self.slot_11 = match object {
-                Person::FirstSing => Some(FinalPersonPrefix::FirstSingHuman),
-                Person::SecondSing => Some(FinalPersonPrefix::SecondSingHuman),
-                Person::ThirdSingHuman => Some(FinalPersonPrefix::ThirdSingHuman),
-                Person::ThirdSingNonHuman => Some(FinalPersonPrefix::ThirdSingNonHuman),
-                _ => todo!("object not implemented for plurals"),
+    pub fn set_object(&mut self, object: Person) -> Result<&mut Self, ConjugationError> {
+        self.object = Some(object);
+        Ok(self)
+    }
+
+    // Places the subject, the object and the oblique object in the slots
+    // that express them, according to the aspect and the transitivity of the verb
+    fn resolve_arguments(&self) -> Result<FiniteVerbalForm, ConjugationError> {
+        let mut verb = self.clone();
+
+        if !verb.is_transitive && verb.object.is_some() {
+            // only a verb of the lexicon made transitive explicitly can take an object
+            return match &self.lexeme {
+                Some(lexeme) if !lexeme.transitive => Err(ConjugationError::IntransitiveLexeme(
+                    lexeme.stem.to_string(),
+                )),
+                _ => Err(ConjugationError::ObjectOnIntransitive),
             };
-        } else {
-            return Err("Cannot set object for intransitive verb".to_string());
         }
-        Ok(self)
+
+        let imperfective_stem = match self.is_perfective {
+            true => None,
+            false => self.imperfective_stem.clone(),
+        };
+
+        // 12.4.3 only the verbs of a reduplication class have a reduplicated imperfective stem,
+        // the other ones keep the imperfective stem of the lexicon
+        let reduplicated_class = match self.lexeme.as_ref().and_then(|lexeme| lexeme.maru) {
+            Some(
+                class @ (lexicon::MaruClass::PartialReduplication
+                | lexicon::MaruClass::FullReduplication),
+            ) => Some(class),
+            _ => None,
+        };
+        let imperfective_stem = match (imperfective_stem, &self.lexeme) {
+            (Some(IpfvStem::Reduplicate), Some(_)) if reduplicated_class.is_none() => None,
+            (imperfective_stem, _) => imperfective_stem,
+        };
+
+        // 12.4.2 the stem depends on the aspect and on the number of the absolutive participant,
+        // unless another stem was given for the imperfective
+        if let Some(lexeme) = &self.lexeme {
+            if !matches!(
+                imperfective_stem,
+                Some(IpfvStem::Reduplicate | IpfvStem::Other(_))
+            ) {
+                let absolutive = match self.is_transitive {
+                    true => &self.object,
+                    false => &self.subject,
+                };
+                let plural_absolutive = absolutive.as_ref().is_some_and(Person::is_plural);
+                verb.slot_12 = lexeme
+                    .stem_for(self.is_perfective, plural_absolutive)
+                    .to_string();
+            }
+        }
+
+        match imperfective_stem {
+            Some(IpfvStem::Reduplicate) => {
+                // 12.4.3 the lexicon tells how the stem is reduplicated,
+                // a stem outside of the lexicon is repeated as a whole
+                verb.slot_12 = match reduplicated_class {
+                    Some(class) => class
+                        .derive(&self.slot_12)
+                        .ok_or_else(|| ConjugationError::MalformedStem(self.slot_12.clone()))?,
+                    None => format!("{}-{}", self.slot_12, self.slot_12),
+                };
+            }
+            Some(IpfvStem::EdMarker) => verb.slot_13 = Some("ed".to_string()),
+            Some(IpfvStem::Other(stem)) => verb.slot_12 = stem,
+            None => (),
+        }
+
+        if let Some(subject) = self.subject.clone() {
+            if !verb.is_transitive || !verb.is_perfective {
+                verb.set_final_person_suffix(Some(subject));
+            } else {
+                // 13.3.1 The final person-prefixes do not have separate plural forms,
+                // a plural agent is expressed by attaching a plural person-suffix
+                // in addition to the final person-prefix
+                verb.set_final_person_prefix(Some(subject.clone()));
+                match subject {
+                    Person::FirstPlur | Person::SecondPlur | Person::ThirdPlurHuman => {
+                        verb.set_final_person_suffix(Some(subject));
+                    }
+                    _ => (),
+                }
+            }
+        }
+
+        if let Some(object) = self.object.clone() {
+            if verb.is_perfective {
+                // 14.1 In perfective transitive forms, the person-suffixes refer to the patient
+                match object {
+                    Person::FirstSing
+                    | Person::SecondSing
+                    | Person::FirstPlur
+                    | Person::SecondPlur
+                    | Person::ThirdPlurHuman => {
+                        if verb.slot_14.is_some() {
+                            // the suffix already marks a plural agent, how both are expressed is not implemented
+                            return Err(ConjugationError::UnsupportedCombination {
+                                slots: vec![MarkerName::FinalPersonSuffix],
+                                reason: "a plural agent cannot be marked with a first, second or plural human patient in the perfective".to_string(),
+                            });
+                        }
+                        verb.set_final_person_suffix(Some(object));
+                    }
+                    // the suffix of a third person object is Ø,
+                    // a plural agent suffix set with the subject must be kept
+                    _ => {
+                        if verb.slot_14.is_none() {
+                            verb.set_final_person_suffix(Some(object));
+                        }
+                    }
+                }
+            } else {
+                verb.set_final_person_prefix(Some(object));
+            }
+        }
+
+        if let Some(LocativePrefix::ObliqueWithFinalPerson) = verb.slot_10 {
+            if verb.is_transitive {
+                // 13.1 the final person-prefix already refers to A or P in transitive forms
+                return Err(ConjugationError::UnsupportedCombination {
+                    slots: vec![MarkerName::LocativePrefix, MarkerName::FinalPersonPrefix],
+                    reason: "the oblique object cannot be expressed by the final person-prefix of a transitive verb".to_string(),
+                });
+            }
+            verb.set_final_person_prefix(self.oblique_object.clone());
+        }
+
+        Ok(verb)
     }
 
     pub fn print_subordinator(&self) -> String {
         if self.slot_15 {
-            return "a".to_string();
+            return "ʔa".to_string();
         }
         "".to_string()
     }
 
-    pub fn print(&mut self) -> Result<String, String> {
+    pub fn print(&mut self) -> Result<String, ConjugationError> {
+        Ok(self.print_segmented()?.surface())
+    }
+
+    pub fn print_segmented(&mut self) -> Result<ConjugatedForm, ConjugationError> {
+        Ok(self.print_traced()?.0)
+    }
+
+    // Also returns the phonological rules applied to build the verb, in order
+    pub fn print_traced(
+        &mut self,
+    ) -> Result<(ConjugatedForm, Vec<RuleApplication>), ConjugationError> {
+        let verb = self.resolve_arguments()?;
+        let morphemes = verb.underlying_slots()?;
+        let mut final_verb = morphemes.clone();
+        let mut trace = Vec::new();
+        phonology::apply_rules(&verb, &mut final_verb, &mut trace);
+
+        Ok((
+            ConjugatedForm::from_slots(&morphemes, &final_verb, &verb.gloss_slots()),
+            trace,
+        ))
+    }
+
+    // Leipzig-style labels for the slots of a resolved verb
+    fn gloss_slots(&self) -> [String; 15] {
+        let mut glosses: [String; 15] = core::array::from_fn(|_| String::new());
+
+        glosses[0] = match self.slot_1 {
+            Some(FirstPrefix::Negative) => "NEG".to_string(),
+            Some(FirstPrefix::Modal) => "MOD".to_string(),
+            None => "".to_string(),
+        };
+        // 23 the vocalic prefix /ʔi/ placed before the coordinator is a finite-marker too
+        if self.slot_2.is_some() || (self.slot_3.is_some() && self.slot_1.is_none()) {
+            glosses[1] = "FIN".to_string();
+        }
+        if self.slot_3.is_some() {
+            glosses[2] = "COOR".to_string();
+        }
+        // the ventive may be added before the initial person-prefix /ʔ/ (16.2.5)
+        glosses[3] = "VEN".to_string();
+        if self.slot_5.is_some() {
+            glosses[4] = "MID".to_string();
+        }
+        if let Some(prefix) = &self.slot_6 {
+            glosses[5] = prefix.gloss();
+        }
+        if let Some(prefix) = &self.slot_7 {
+            glosses[6] = format!("{}.DAT", prefix.gloss());
+        }
+        if self.slot_8.is_some() {
+            glosses[7] = "COM".to_string();
+        }
+        glosses[8] = match self.slot_9 {
+            Some(AdverbialPrefix::Ablative) => "ABL".to_string(),
+            Some(AdverbialPrefix::Terminative) => "TERM".to_string(),
+            None => "".to_string(),
+        };
+        glosses[9] = match self.slot_10 {
+            Some(LocativePrefix::InWithInitialPerson | LocativePrefix::InWithoutInitialPerson) => {
+                "LOC".to_string()
+            }
+            Some(LocativePrefix::OnWithInitialPerson | LocativePrefix::OnWithoutInitialPerson) => {
+                "LOC2".to_string()
+            }
+            Some(LocativePrefix::ObliqueSecondSing) => "2SG.LOC3".to_string(),
+            Some(LocativePrefix::ObliqueWithFinalPerson) => "LOC3".to_string(),
+            None => "".to_string(),
+        };
+        if let Some(prefix) = &self.slot_11 {
+            glosses[10] = if let Some(LocativePrefix::ObliqueWithFinalPerson) = self.slot_10 {
+                format!("{}.OBL", prefix.gloss())
+            } else if self.is_transitive && self.is_perfective {
+                format!("{}.A", prefix.gloss())
+            } else if self.is_transitive {
+                format!("{}.P", prefix.gloss())
+            } else {
+                prefix.gloss()
+            };
+        }
+        glosses[11] = match &self.lexeme {
+            Some(lexeme) => lexeme.gloss(),
+            None => lexicon::Lexicon::builtin_ref()
+                .find_verb_by_form(&self.slot_12)
+                .map(lexicon::LexiconEntry::gloss)
+                .unwrap_or(self.slot_12.clone()),
+        };
+        if self.slot_13.is_some() {
+            glosses[12] = "PF".to_string();
+        }
+        if let Some(suffix) = &self.slot_14 {
+            let refers_to_patient = matches!(
+                self.object,
+                Some(
+                    Person::FirstSing
+                        | Person::SecondSing
+                        | Person::FirstPlur
+                        | Person::SecondPlur
+                        | Person::ThirdPlurHuman
+                )
+            );
+            glosses[13] = if !self.is_transitive {
+                format!("{}.S", suffix.gloss())
+            } else if self.is_perfective && refers_to_patient {
+                format!("{}.P", suffix.gloss())
+            } else {
+                format!("{}.A", suffix.gloss())
+            };
+        }
+        if self.slot_15 {
+            glosses[14] = "NMLZ".to_string();
+        }
+
+        glosses
+    }
+
+    // 1- Populates the different slots with the respective morphems
+    #[allow(clippy::single_match)]
+    fn underlying_slots(&self) -> Result<[String; 15], ConjugationError> {
         let final_verb: &mut [String; 15] = &mut core::array::from_fn(|_| String::new());
-        // 1- Populates the different slots with the respective morphems
 
         // STEM
         if self.slot_12.is_empty() {
-            return Err("Stem cannot be empty".to_string());
+            return Err(ConjugationError::EmptyStem);
         } else {
             final_verb.add_stem(self.slot_12.clone());
         }
         // SUFFIXES
         match self.slot_13.clone() {
-            Some(marker) => {
-                if ends_with_vowel(&self.slot_12) {
-                    final_verb.add_ed_marker("d".to_string());
-                } else {
-                    final_verb.add_ed_marker(marker)
-                }
-            }
+            Some(marker) => final_verb.add_ed_marker(marker),
             None => (),
         };
-        let has_final_ps_suffix = match self.clone().slot_14 {
+        match self.clone().slot_14 {
             Some(suffix) => {
-                final_verb.add_final_ps_suffix(suffix.output(&self));
-                true
+                final_verb.add_final_ps_suffix(suffix.output(self));
             }
-            None => false,
+            None => (),
+        };
+        if self.slot_15 {
+            final_verb.add_subordinator(self.print_subordinator());
         };
 
         // PREFIXES
         // NEGATIVE PREFIX
-        let has_modal = match self.clone().slot_1 {
-            Some(prefix) => match prefix {
-                FirstPrefix::Negative => {
-                    final_verb.add_negative_prefix();
-                    false
-                }
-                FirstPrefix::Modal => {
-                    final_verb.add_modal_prefix(None);
-                    true
-                }
-            },
-            None => false,
+        match self.clone().slot_1 {
+            Some(FirstPrefix::Negative) => final_verb.add_negative_prefix(),
+            Some(FirstPrefix::Modal) => final_verb.add_modal_prefix(None),
+            None => (),
         };
         // PREFORMATIVE MARKER
-        let has_preformative = match self.slot_2.clone() {
+        match self.slot_2.clone() {
             Some(preformative) => {
                 final_verb.add_preformative_prefix(preformative.output());
-                true
             }
-            None => false,
+            None => (),
+        };
+        // COORDINATOR
+        if self.slot_3.is_some() {
+            final_verb.add_coordinator("nga".to_string());
         };
         // VENTIVE
-        let has_ventive = if self.clone().slot_4.is_some() {
+        if self.clone().slot_4.is_some() {
             final_verb.add_ventive("mu".to_string());
-            true
-        } else {
-            false
         };
         // MIDDLE PREFIX
-        let has_middle_prefix = if self.clone().slot_5.is_some() {
+        if self.clone().slot_5.is_some() {
             final_verb.add_middle_prefix("ba".to_string());
-            true
-        } else {
-            false
         };
         // INITIAL PERSON PREFIX
-        let has_initial_person_prefix = match &self.slot_6 {
+        match &self.slot_6 {
             Some(prefix) => {
                 let prefix_output = match prefix {
                     InitialPersonPrefix::FirstSing => "ʔ".to_string(),
@@ -416,12 +701,11 @@ impl FiniteVerbalForm {
                     InitialPersonPrefix::ThirdPlurNonHuman => "b".to_string(),
                 };
                 final_verb.add_initial_person_prefix(prefix_output);
-                true
             }
-            None => false,
+            None => (),
         };
         // INDIRECT OBJECT MARKER
-        let has_indirect_object = match self.clone().slot_7 {
+        match self.clone().slot_7 {
             Some(prefix) => {
                 let prefix_output = match prefix {
                     IndirectObjectPrefix::FirstSing => "ma".to_string(),
@@ -434,9 +718,8 @@ impl FiniteVerbalForm {
                     IndirectObjectPrefix::ThirdPlurNonHuman => "ba".to_string(),
                 };
                 final_verb.add_indirect_object(prefix_output);
-                true
             }
-            None => false,
+            None => (),
         };
         // COMITATIVE
         if self.clone().slot_8.is_some() {
@@ -455,266 +738,30 @@ impl FiniteVerbalForm {
             _ => (),
         }
         // LOCATIVE PREFIXES
-        let must_update_locative = match self.slot_10.clone() {
+        match self.slot_10.clone() {
             Some(prefix) => {
                 // 20.1
-                let (prefix_output, result) = match prefix {
-                    LocativePrefix::InWithInitialPerson => ("".to_string(), false),
-                    LocativePrefix::InWithoutInitialPerson => ("ni".to_string(), true),
-                    LocativePrefix::OnWithInitialPerson => ("bi".to_string(), false),
-                    LocativePrefix::OnWithoutInitialPerson => ("e".to_string(), true),
+                let prefix_output = match prefix {
+                    LocativePrefix::InWithInitialPerson => "".to_string(),
+                    LocativePrefix::InWithoutInitialPerson => "ni".to_string(),
+                    LocativePrefix::OnWithInitialPerson => "bi".to_string(),
+                    LocativePrefix::OnWithoutInitialPerson => "e".to_string(),
+                    LocativePrefix::ObliqueSecondSing => "ri".to_string(),
+                    LocativePrefix::ObliqueWithFinalPerson => "".to_string(),
                 };
                 final_verb.add_locative_prefix(prefix_output);
-                result
             }
-            None => false,
+            None => (),
         };
         // FINAL PERSON PREFIX
         match self.clone().slot_11 {
             Some(prefix) => {
-                let prefix_output = match prefix {
-                    FinalPersonPrefix::SecondSingHuman => {
-                        // 13.2.4 The prefix {e} contracts with a preceding vowel, lengthening that vowel
-                        match final_verb.find_previous_morphem(10) {
-                            Some(morphem) => {
-                                if morphem.ends_with("a") {
-                                    String::from("a")
-                                } else if morphem.ends_with("i") {
-                                    String::from("i")
-                                } else if morphem.ends_with("u") {
-                                    String::from("u")
-                                } else {
-                                    prefix.output()
-                                }
-                            }
-                            None => prefix.output(),
-                        }
-                    }
-                    _ => prefix.output(),
-                };
-                final_verb.add_final_ps_prefix(prefix_output);
+                final_verb.add_final_ps_prefix(prefix.output(self));
             }
             None => (),
         };
 
-        // 2- Usage rules
-        if has_initial_person_prefix {
-            if let Some(InitialPersonPrefix::FirstSing) = self.slot_6.clone() {
-                // 16.2.5 In the texts of our corpus, the ventive prefix {mu} (chapter 17)
-                // is always used before the initial person-prefix /ʔ/ and always has the form /mu/
-                final_verb.add_ventive("mu".to_string());
-            }
-        }
-
-        // 3- Updates the morphems according to their phonologic environments
-        if has_ventive {
-            if has_middle_prefix {
-                // 21.2 Only after the ventive prefix (§22.2), {ba} has a slightly different form,
-                // because the /b/ of {ba} assimilates to the preceding /m/ of the ventive.
-                final_verb.add_ventive(String::from("m"));
-                final_verb.add_middle_prefix("ma".to_string());
-            }
-            if has_indirect_object {
-                // 17.2.1 After the ventive prefix (§22.2), the prefix {ba} has a slighly different form,
-                // because the /b/ of {ba} assimilates to the preceding /m/ of the ventive:
-                match self.slot_7.clone() {
-                    Some(prefix) => {
-                        match prefix {
-                            IndirectObjectPrefix::ThirdSingNonHuman
-                            | IndirectObjectPrefix::ThirdPlurNonHuman => {
-                                final_verb.add_ventive(String::from("m"));
-                                final_verb.add_indirect_object(String::from("ma"));
-                            }
-                            _ => (),
-                        };
-                    }
-                    None => (),
-                }
-            }
-        }
-
-        if has_preformative {
-            if has_modal {
-                // If the verbal form begins with the vocalic prefix /ʔi/ (§24.3),
-                // /ḫa/ contracts with it. The sequence /ḫaʔi/ thus becomes /ḫē/
-                match &self.slot_2 {
-                    Some(Preformative::I) => {
-                        final_verb.add_preformative_prefix("".to_string());
-                        final_verb.add_modal_prefix(Some("ḫē".to_string()));
-                    }
-                    _ => (),
-                }
-            } else {
-                // TODO: 24.3.1 they are never found before a prefix with the shape /CV/.
-                // Instead of a vocalic prefix we then find zero, that is, no preformative at all.
-                // 24.3.2 The prefix {ʔi} may also contract with the verbal stem,
-                // if the latter has an initial glottal stop.
-                match final_verb.find_previous_morphem(1) {
-                    // looks for previous morphem, if any
-                    Some(morphem) => {
-                        if morphem.ends_with("u") {
-                            final_verb.add_preformative_prefix("u".to_string());
-                        }
-                    }
-                    None => {
-                        // if no previous morphem, looks for the next morphem
-                        // and checks if it is the stem
-                        match final_verb.find_following_morphem(2) {
-                            // looks for previous morphem, if any
-                            Some((morphem, morphem_name)) => {
-                                if morphem.starts_with("u") && morphem_name == MarkerName::Stem {
-                                    final_verb.add_preformative_prefix("u".to_string());
-                                }
-                            }
-                            None => (),
-                        }
-                    }
-                }
-            }
-        }
-
-        if has_final_ps_suffix {
-            // 14.1 First, the /e/ contracts with a preceding vowel.
-            // Secondly, the /e/ may assimilate to a stem vowel /u/ or /i/.
-            //TODO: Finally, the /e/ may be reduced in forms with the nominalizing suffix {ʔa}.
-            match final_verb.find_previous_morphem(12) {
-                Some(morphem) => {
-                    match final_verb.find_final_ps_suffix() {
-                        None => (),
-                        Some(suffix) => {
-                            // current suffix in the verbal form
-                            // FIXME: leaving "e" untouched for now
-                            if suffix.len() > 1
-                                && (morphem.ends_with("a")
-                                    || morphem.ends_with("i")
-                                    || morphem.ends_with("u")
-                                    || morphem.ends_with("e"))
-                            {
-                                let mut truncated_suffix = suffix;
-                                truncated_suffix.remove(0);
-                                final_verb.add_final_ps_suffix(format!("{}", truncated_suffix));
-                            }
-                        }
-                    }
-                }
-                None => (),
-            }
-        }
-
-        if has_initial_person_prefix {
-            match self.slot_6.clone() {
-                None => (),
-                Some(prefix) => {
-                    match prefix {
-                        // First, the prefix {b} cannot occur between the ventive prefix and a consonant (see §22.4).
-                        // Second, between the form /m/ of the ventive and a vowel, the prefix {b} assimilates to the /m/.
-                        InitialPersonPrefix::ThirdSingNonHuman => {
-                            match self.slot_4.clone() {
-                                None => (),
-                                Some(_) => {
-                                    // has ventive, checks the marker
-                                    match self.slot_7.clone() {
-                                        Some(_) => {
-                                            // has a dative marker
-                                        }
-                                        None => match self.slot_8.clone() {
-                                            Some(_) => {
-                                                // has a comitative marker
-                                                final_verb
-                                                    .add_initial_person_prefix("".to_string());
-                                                final_verb.add_ventive(String::from("m"));
-                                            }
-                                            None => match self.slot_9.clone() {
-                                                Some(_) => {
-                                                    // has an adverbial marker
-                                                    final_verb
-                                                        .add_initial_person_prefix("".to_string());
-                                                    final_verb.add_ventive(String::from("m"));
-                                                }
-                                                None => match self.slot_10.clone() {
-                                                    Some(_) => {
-                                                        // has a locative marker
-                                                        todo!("remove initial person prefix with locative marker and ventive")
-                                                    }
-                                                    None => (),
-                                                },
-                                            },
-                                        },
-                                    }
-                                }
-                            }
-                        }
-                        InitialPersonPrefix::SecondSing => {
-                            // 16.2.4 The prefix {e} contracts with a preceding vowel, lengthening that vowel.
-                            match final_verb.find_previous_morphem(5) {
-                                Some(morphem) => {
-                                    if morphem.ends_with("a") {
-                                        final_verb.add_initial_person_prefix(String::from("a"))
-                                    } else if morphem.ends_with("i") {
-                                        final_verb.add_initial_person_prefix(String::from("i"))
-                                    } else if morphem.ends_with("u") {
-                                        final_verb.add_initial_person_prefix(String::from("u"))
-                                    } else {
-                                        ()
-                                    }
-                                }
-                                None => (),
-                            }
-                        }
-                        _ => (),
-                    }
-                }
-            }
-        }
-
-        if must_update_locative {
-            // 20.1
-            let prefix_output = match &self.slot_10 {
-                Some(LocativePrefix::InWithoutInitialPerson) => match &self.slot_11 {
-                    Some(_) => "ni".to_string(),
-                    None => "n".to_string(),
-                },
-                Some(LocativePrefix::OnWithInitialPerson) => match &self.slot_11 {
-                    Some(_) => "bi".to_string(),
-                    None => "b".to_string(),
-                },
-                Some(LocativePrefix::OnWithoutInitialPerson) => match &self.slot_11 {
-                    Some(_) => "".to_string(),
-                    None => "e".to_string(),
-                },
-                _ => {
-                    // BUG: it may not be the best idea, check later
-                    final_verb[9].clone()
-                }
-            };
-            final_verb.add_locative_prefix(prefix_output);
-        }
-
-        // Updates ventive form
-        // 22.2 Before the indirect-object prefix {ra}, the oblique-object prefix {ri},
-        // and the local prefix {ni}, however, the /u/ is always retained
-        // but may assimilate to the vowel of the following syllable.
-        if has_ventive {
-            match final_verb.find_following_morphem(MarkerName::Ventive.position() + 1) {
-                Some((morphem, marker_name)) => {
-                    if morphem == String::from("ni") && marker_name == MarkerName::LocativePrefix {
-                        final_verb.add_ventive(String::from("mi"));
-                    } else if morphem == String::from("ra")
-                        && marker_name == MarkerName::DativePrefix
-                    {
-                        final_verb.add_ventive(String::from("ma"));
-                    } else {
-                        ()
-                    }
-                }
-                None => (),
-            }
-        }
-
-        // println!("Built verb: {:#?}", self);
-        // println!("Final verb: {:#?}", final_verb);
-
-        Ok(final_verb.print())
+        Ok(final_verb.clone())
     }
 }
 
@@ -758,6 +805,85 @@ impl MarkerName {
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlotChange {
+    pub slot: MarkerName,
+    pub before: String,
+    pub after: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleApplication {
+    pub section: Option<String>, // section of the grammar describing the rule
+    pub description: String,
+    pub changes: Vec<SlotChange>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Morph {
+    pub slot: MarkerName,
+    pub morpheme: String, // underlying form, before the phonological rules
+    pub surface: String,  // realization in the final verb, may be empty
+    pub gloss: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConjugatedForm {
+    pub morphs: Vec<Morph>,
+}
+impl ConjugatedForm {
+    fn from_slots(
+        morphemes: &[String; 15],
+        final_verb: &[String; 15],
+        glosses: &[String; 15],
+    ) -> Self {
+        let morphs = morphemes
+            .iter()
+            .zip(final_verb.iter())
+            .zip(glosses.iter())
+            .enumerate()
+            .filter(|(_, ((morpheme, surface), _))| !morpheme.is_empty() || !surface.is_empty())
+            .filter_map(|(i, ((morpheme, surface), gloss))| {
+                <[String; 15]>::name_by_position(i).map(|slot| Morph {
+                    slot,
+                    morpheme: morpheme.clone(),
+                    surface: surface.clone(),
+                    gloss: gloss.clone(),
+                })
+            })
+            .collect();
+        ConjugatedForm { morphs }
+    }
+
+    pub fn surface(&self) -> String {
+        self.morphs
+            .iter()
+            .map(|morph| morph.surface.clone())
+            .collect::<Vec<String>>()
+            .join("")
+    }
+
+    // e.g. "mu-n-zu"
+    pub fn hyphenated(&self) -> String {
+        self.morphs
+            .iter()
+            .filter(|morph| !morph.surface.is_empty())
+            .map(|morph| morph.surface.clone())
+            .collect::<Vec<String>>()
+            .join("-")
+    }
+
+    // e.g. "VEN-3SG.H.A-know", aligned with the hyphenated form
+    pub fn gloss(&self) -> String {
+        self.morphs
+            .iter()
+            .filter(|morph| !morph.surface.is_empty())
+            .map(|morph| morph.gloss.clone())
+            .collect::<Vec<String>>()
+            .join("-")
+    }
+}
+
 // pub const FINAL_VERB: [String; 15] = ["", "", "", "", "", "", "", "", "", "", "", "", "", "", ""];
 pub trait FinalVerbImpl {
     fn add_stem(&mut self, new_stem: String);
@@ -766,6 +892,7 @@ pub trait FinalVerbImpl {
     fn add_subordinator(&mut self, subordinator: String);
     fn add_preformative_prefix(&mut self, preformative: String);
     fn add_final_ps_prefix(&mut self, prefix: String);
+    fn add_coordinator(&mut self, coordinator: String);
     fn add_negative_prefix(&mut self);
     fn add_modal_prefix(&mut self, prefix: Option<String>);
     fn add_ventive(&mut self, ventive: String);
@@ -780,7 +907,7 @@ pub trait FinalVerbImpl {
     fn find_following_morphem(&self, starting_slot: usize) -> Option<(String, MarkerName)>;
     fn find_first_morpheme(&self) -> Option<String>;
     fn find_final_ps_suffix(&self) -> Option<String>;
-    fn name_by_position(position: usize) -> Result<MarkerName, ()>;
+    fn name_by_position(position: usize) -> Option<MarkerName>;
 
     fn print(&mut self) -> String;
 }
@@ -804,6 +931,9 @@ impl FinalVerbImpl for [String; 15] {
     fn add_final_ps_prefix(&mut self, prefix: String) {
         self[10] = prefix;
     }
+    fn add_coordinator(&mut self, coordinator: String) {
+        self[2] = coordinator;
+    }
     fn add_negative_prefix(&mut self) {
         self[0] = "nu".to_string();
     }
@@ -838,74 +968,73 @@ impl FinalVerbImpl for [String; 15] {
         self[9] = prefix;
     }
     fn find_previous_morphem(&self, starting_slot: usize) -> Option<String> {
-        for i in (0..starting_slot).rev() {
-            if !self[i].is_empty() {
-                return Some(self[i].clone());
-            }
-        }
-        None
+        self[..starting_slot]
+            .iter()
+            .rev()
+            .find(|morphem| !morphem.is_empty())
+            .cloned()
     }
     fn find_following_morphem(&self, starting_slot: usize) -> Option<(String, MarkerName)> {
-        for i in starting_slot..self.len() {
-            if !self[i].is_empty() {
-                match Self::name_by_position(i) {
-                    Ok(name) => return Some((self[i].clone(), name)),
-                    Err(_) => (),
+        for (i, morphem) in self.iter().enumerate().skip(starting_slot) {
+            if !morphem.is_empty() {
+                if let Some(name) = Self::name_by_position(i) {
+                    return Some((morphem.clone(), name));
                 }
             }
         }
         None
     }
     fn find_first_morpheme(&self) -> Option<String> {
-        for i in 0..self.len() {
-            if !self[i].is_empty() {
-                return Some(self[i].clone());
-            }
-        }
-        None
+        self.iter().find(|morphem| !morphem.is_empty()).cloned()
     }
     fn find_final_ps_suffix(&self) -> Option<String> {
         let suffix = self[13].clone();
         if suffix.is_empty() {
             return None;
         }
-        return Some(suffix);
+        Some(suffix)
     }
-    fn name_by_position(position: usize) -> Result<MarkerName, ()> {
+    fn name_by_position(position: usize) -> Option<MarkerName> {
         match position {
-            0 => Ok(MarkerName::FirstPrefix),
-            1 => Ok(MarkerName::Preformative),
-            2 => Ok(MarkerName::Coordinator),
-            3 => Ok(MarkerName::Ventive),
-            4 => Ok(MarkerName::MiddlePrefix),
-            5 => Ok(MarkerName::InitialPronominalPrefix),
-            6 => Ok(MarkerName::DativePrefix),
-            7 => Ok(MarkerName::ComitativePrefix),
-            8 => Ok(MarkerName::AdverbialPrefix),
-            9 => Ok(MarkerName::LocativePrefix),
-            10 => Ok(MarkerName::FinalPersonPrefix),
-            11 => Ok(MarkerName::Stem),
-            12 => Ok(MarkerName::EdMarker),
-            13 => Ok(MarkerName::FinalPersonSuffix),
-            14 => Ok(MarkerName::Subordinator),
-            _ => Err(()),
+            0 => Some(MarkerName::FirstPrefix),
+            1 => Some(MarkerName::Preformative),
+            2 => Some(MarkerName::Coordinator),
+            3 => Some(MarkerName::Ventive),
+            4 => Some(MarkerName::MiddlePrefix),
+            5 => Some(MarkerName::InitialPronominalPrefix),
+            6 => Some(MarkerName::DativePrefix),
+            7 => Some(MarkerName::ComitativePrefix),
+            8 => Some(MarkerName::AdverbialPrefix),
+            9 => Some(MarkerName::LocativePrefix),
+            10 => Some(MarkerName::FinalPersonPrefix),
+            11 => Some(MarkerName::Stem),
+            12 => Some(MarkerName::EdMarker),
+            13 => Some(MarkerName::FinalPersonSuffix),
+            14 => Some(MarkerName::Subordinator),
+            _ => None,
         }
     }
 
     fn print(&mut self) -> String {
-        format!("{}", self.join(""))
+        self.join("")
     }
 }
 
 type Stem = String;
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum IpfvStem {
     Reduplicate,
     EdMarker,
     Other(String),
 }
 
+#[wasm_bindgen]
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum Preformative {
     A,
     I,
@@ -920,62 +1049,186 @@ impl Preformative {
         }
     }
 }
+impl std::str::FromStr for Preformative {
+    type Err = ConjugationError;
+
+    fn from_str(preformative: &str) -> Result<Self, Self::Err> {
+        match preformative.to_lowercase().as_str() {
+            "a" => Ok(Preformative::A),
+            "i" => Ok(Preformative::I),
+            "u" => Ok(Preformative::U),
+            _ => Err(ConjugationError::InvalidSpecification(format!(
+                "unknown preformative '{}'",
+                preformative
+            ))),
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum IndirectObjectPrefix {
+    #[cfg_attr(feature = "serde", serde(rename = "1sg"))]
     FirstSing,
+    #[cfg_attr(feature = "serde", serde(rename = "2sg"))]
     SecondSing,
+    #[cfg_attr(feature = "serde", serde(rename = "3sg.h"))]
     ThirdSingHuman,
+    #[cfg_attr(feature = "serde", serde(rename = "3sg.nh"))]
     ThirdSingNonHuman,
+    #[cfg_attr(feature = "serde", serde(rename = "1pl"))]
     FirstPlur,
+    #[cfg_attr(feature = "serde", serde(rename = "2pl"))]
     SecondPlur,
+    #[cfg_attr(feature = "serde", serde(rename = "3pl.h"))]
     ThirdPlurHuman,
+    #[cfg_attr(feature = "serde", serde(rename = "3pl.nh"))]
     ThirdPlurNonHuman,
 }
 
+impl IndirectObjectPrefix {
+    fn gloss(&self) -> String {
+        match self {
+            IndirectObjectPrefix::FirstSing => "1SG".to_string(),
+            IndirectObjectPrefix::SecondSing => "2SG".to_string(),
+            IndirectObjectPrefix::ThirdSingHuman => "3SG.H".to_string(),
+            IndirectObjectPrefix::ThirdSingNonHuman => "3SG.NH".to_string(),
+            IndirectObjectPrefix::FirstPlur => "1PL".to_string(),
+            IndirectObjectPrefix::SecondPlur => "2PL".to_string(),
+            IndirectObjectPrefix::ThirdPlurHuman => "3PL.H".to_string(),
+            IndirectObjectPrefix::ThirdPlurNonHuman => "3PL.NH".to_string(),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum InitialPersonPrefix {
+    #[cfg_attr(feature = "serde", serde(rename = "1sg"))]
     FirstSing,
+    #[cfg_attr(feature = "serde", serde(rename = "2sg"))]
     SecondSing,
+    #[cfg_attr(feature = "serde", serde(rename = "3sg.h"))]
     ThirdSingHuman,
+    #[cfg_attr(feature = "serde", serde(rename = "3sg.nh"))]
     ThirdSingNonHuman,
+    #[cfg_attr(feature = "serde", serde(rename = "1pl"))]
     FirstPlur,
+    #[cfg_attr(feature = "serde", serde(rename = "2pl"))]
     SecondPlur,
+    #[cfg_attr(feature = "serde", serde(rename = "3pl.h"))]
     ThirdPlurHuman,
+    #[cfg_attr(feature = "serde", serde(rename = "3pl.nh"))]
     ThirdPlurNonHuman,
 }
+impl InitialPersonPrefix {
+    fn gloss(&self) -> String {
+        match self {
+            InitialPersonPrefix::FirstSing => "1SG".to_string(),
+            InitialPersonPrefix::SecondSing => "2SG".to_string(),
+            InitialPersonPrefix::ThirdSingHuman => "3SG.H".to_string(),
+            InitialPersonPrefix::ThirdSingNonHuman => "3SG.NH".to_string(),
+            InitialPersonPrefix::FirstPlur => "1PL".to_string(),
+            InitialPersonPrefix::SecondPlur => "2PL".to_string(),
+            InitialPersonPrefix::ThirdPlurHuman => "3PL.H".to_string(),
+            InitialPersonPrefix::ThirdPlurNonHuman => "3PL.NH".to_string(),
+        }
+    }
+}
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FinalPersonPrefix {
+    #[cfg_attr(feature = "serde", serde(rename = "1sg"))]
     FirstSingHuman,
+    #[cfg_attr(feature = "serde", serde(rename = "2sg"))]
     SecondSingHuman,
+    #[cfg_attr(feature = "serde", serde(rename = "3sg.h"))]
     ThirdSingHuman,
+    #[cfg_attr(feature = "serde", serde(rename = "3sg.nh"))]
     ThirdSingNonHuman,
+    #[cfg_attr(feature = "serde", serde(rename = "1pl"))]
+    FirstPlurHuman,
+    #[cfg_attr(feature = "serde", serde(rename = "2pl"))]
+    SecondPlurHuman,
+    #[cfg_attr(feature = "serde", serde(rename = "3pl.h"))]
+    ThirdPlurHuman,
+    #[cfg_attr(feature = "serde", serde(rename = "3pl.nh"))]
+    ThirdPlurNonHuman,
 }
 impl FinalPersonPrefix {
-    fn output(&self) -> String {
+    fn gloss(&self) -> String {
+        match self {
+            FinalPersonPrefix::FirstSingHuman => "1SG".to_string(),
+            FinalPersonPrefix::SecondSingHuman => "2SG".to_string(),
+            FinalPersonPrefix::ThirdSingHuman => "3SG.H".to_string(),
+            FinalPersonPrefix::ThirdSingNonHuman => "3SG.NH".to_string(),
+            FinalPersonPrefix::FirstPlurHuman => "1PL".to_string(),
+            FinalPersonPrefix::SecondPlurHuman => "2PL".to_string(),
+            FinalPersonPrefix::ThirdPlurHuman => "3PL.H".to_string(),
+            FinalPersonPrefix::ThirdPlurNonHuman => "3PL.NH".to_string(),
+        }
+    }
+    // 13.3.1 The final person-prefixes do not have separate singular and plural forms
+    fn output(&self, verb: &FiniteVerbalForm) -> String {
         match self {
-            FinalPersonPrefix::FirstSingHuman => "ʔ".to_string(),
-            FinalPersonPrefix::SecondSingHuman => "e".to_string(),
+            FinalPersonPrefix::FirstSingHuman | FinalPersonPrefix::FirstPlurHuman => {
+                "ʔ".to_string()
+            }
+            FinalPersonPrefix::SecondSingHuman | FinalPersonPrefix::SecondPlurHuman => {
+                "e".to_string()
+            }
             FinalPersonPrefix::ThirdSingHuman => "n".to_string(),
-            FinalPersonPrefix::ThirdSingNonHuman => "b".to_string(),
+            FinalPersonPrefix::ThirdPlurHuman => {
+                // the non-human {b} refers to a plural number of persons
+                // when there is no plural person-suffix to disambiguate the prefix
+                if verb.is_transitive && !verb.is_perfective {
+                    "b".to_string()
+                } else {
+                    "n".to_string()
+                }
+            }
+            FinalPersonPrefix::ThirdSingNonHuman | FinalPersonPrefix::ThirdPlurNonHuman => {
+                "b".to_string()
+            }
         }
     }
 }
 
 // a person suffix is the only one that is basically always present.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FinalPersonSuffix {
+    #[cfg_attr(feature = "serde", serde(rename = "1sg"))]
     FirstSingHuman,
+    #[cfg_attr(feature = "serde", serde(rename = "2sg"))]
     SecondSingHuman,
+    #[cfg_attr(feature = "serde", serde(rename = "3sg.h"))]
     ThirdSingHuman,
+    #[cfg_attr(feature = "serde", serde(rename = "3sg.nh"))]
     ThirdSingNonHuman,
+    #[cfg_attr(feature = "serde", serde(rename = "1pl"))]
     FirstPlurHuman,
+    #[cfg_attr(feature = "serde", serde(rename = "2pl"))]
     SecondPlurHuman,
+    #[cfg_attr(feature = "serde", serde(rename = "3pl.h"))]
     ThirdPlurHuman,
+    #[cfg_attr(feature = "serde", serde(rename = "3pl.nh"))]
     ThirdPlurNonHuman,
 }
 impl FinalPersonSuffix {
+    fn gloss(&self) -> String {
+        match self {
+            FinalPersonSuffix::FirstSingHuman => "1SG".to_string(),
+            FinalPersonSuffix::SecondSingHuman => "2SG".to_string(),
+            FinalPersonSuffix::ThirdSingHuman => "3SG.H".to_string(),
+            FinalPersonSuffix::ThirdSingNonHuman => "3SG.NH".to_string(),
+            FinalPersonSuffix::FirstPlurHuman => "1PL".to_string(),
+            FinalPersonSuffix::SecondPlurHuman => "2PL".to_string(),
+            FinalPersonSuffix::ThirdPlurHuman => "3PL.H".to_string(),
+            FinalPersonSuffix::ThirdPlurNonHuman => "3PL.NH".to_string(),
+        }
+    }
     fn output(&self, verb: &FiniteVerbalForm) -> String {
         match self {
             FinalPersonSuffix::FirstSingHuman => "en".to_string(),
@@ -983,82 +1236,175 @@ impl FinalPersonSuffix {
             FinalPersonSuffix::ThirdSingHuman | FinalPersonSuffix::ThirdSingNonHuman => {
                 // can be Ø or "e"
                 if verb.is_transitive && !verb.is_perfective {
-                    return "e".to_string();
+                    "e".to_string()
                 } else {
-                    return "".to_string();
+                    "".to_string()
                 }
             }
             FinalPersonSuffix::FirstPlurHuman => "enden".to_string(),
             FinalPersonSuffix::SecondPlurHuman => "enzen".to_string(),
             FinalPersonSuffix::ThirdPlurHuman => {
                 // can be "eš" or "enē"
-                // return "enē".to_string();
-                return "eš".to_string();
+                // "enē".to_string()
+                "eš".to_string()
             }
             FinalPersonSuffix::ThirdPlurNonHuman => {
-                return "".to_string(); // 15.3.3
+                "".to_string() // 15.3.3
             }
         }
     }
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum FirstPrefix {
     Negative, // nu
     Modal,    // ha
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Coordinator;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Ventive;
 
 #[derive(Debug, Clone)]
 pub struct DativePrefix;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ComitativePrefix;
 
+#[wasm_bindgen]
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum AdverbialPrefix {
     Ablative,
     Terminative,
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum LocativePrefix {
     InWithInitialPerson,
     InWithoutInitialPerson,
     OnWithInitialPerson,
     OnWithoutInitialPerson,
+    ObliqueSecondSing,
+    ObliqueWithFinalPerson,
 }
+impl std::str::FromStr for LocativePrefix {
+    type Err = ConjugationError;
 
-#[derive(Debug, Clone)]
+    // "in" and "on" stand for the prefixes without initial person-prefix
+    fn from_str(locative: &str) -> Result<Self, Self::Err> {
+        match locative {
+            "in" => Ok(LocativePrefix::InWithoutInitialPerson),
+            "on" => Ok(LocativePrefix::OnWithoutInitialPerson),
+            "in_with_initial_person" => Ok(LocativePrefix::InWithInitialPerson),
+            "on_with_initial_person" => Ok(LocativePrefix::OnWithInitialPerson),
+            "oblique_second_sing" => Ok(LocativePrefix::ObliqueSecondSing),
+            _ => Err(ConjugationError::InvalidSpecification(format!(
+                "unknown locative '{}'",
+                locative
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MiddlePrefix;
 
 /*
     ARGUMENTS FOR VERB CONSTRUCTION
 */
+#[wasm_bindgen]
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Person {
+    #[cfg_attr(feature = "serde", serde(rename = "1sg"))]
     FirstSing,
+    #[cfg_attr(feature = "serde", serde(rename = "2sg"))]
     SecondSing,
+    #[cfg_attr(feature = "serde", serde(rename = "3sg.h"))]
     ThirdSingHuman,
+    #[cfg_attr(feature = "serde", serde(rename = "3sg.nh"))]
     ThirdSingNonHuman,
+    #[cfg_attr(feature = "serde", serde(rename = "1pl"))]
     FirstPlur,
+    #[cfg_attr(feature = "serde", serde(rename = "2pl"))]
     SecondPlur,
+    #[cfg_attr(feature = "serde", serde(rename = "3pl.h"))]
     ThirdPlurHuman,
+    #[cfg_attr(feature = "serde", serde(rename = "3pl.nh"))]
     ThirdPlurNonHuman,
 }
 
+impl Person {
+    pub fn is_plural(&self) -> bool {
+        matches!(
+            self,
+            Person::FirstPlur
+                | Person::SecondPlur
+                | Person::ThirdPlurHuman
+                | Person::ThirdPlurNonHuman
+        )
+    }
+    // e.g. "3sg.h", as written in verb specifications
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            Person::FirstSing => "1sg",
+            Person::SecondSing => "2sg",
+            Person::ThirdSingHuman => "3sg.h",
+            Person::ThirdSingNonHuman => "3sg.nh",
+            Person::FirstPlur => "1pl",
+            Person::SecondPlur => "2pl",
+            Person::ThirdPlurHuman => "3pl.h",
+            Person::ThirdPlurNonHuman => "3pl.nh",
+        }
+    }
+}
+impl std::str::FromStr for Person {
+    type Err = ConjugationError;
+
+    fn from_str(abbreviation: &str) -> Result<Self, Self::Err> {
+        PERSONS
+            .into_iter()
+            .find(|person| person.abbreviation() == abbreviation.to_lowercase())
+            .ok_or(ConjugationError::InvalidSpecification(format!(
+                "unknown person '{}'",
+                abbreviation
+            )))
+    }
+}
+
+pub const PERSONS: [Person; 8] = [
+    Person::FirstSing,
+    Person::SecondSing,
+    Person::ThirdSingHuman,
+    Person::ThirdSingNonHuman,
+    Person::FirstPlur,
+    Person::SecondPlur,
+    Person::ThirdPlurHuman,
+    Person::ThirdPlurNonHuman,
+];
+
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DimensionalPrefixes {
     pub indirect_object: Option<Person>,
     pub comitative: bool,
     pub locative: Option<LocativePrefix>,
     pub ablative: bool,
     pub terminative: bool,
+    // person of the comitative, ablative or terminative prefix
+    pub initial_person: Option<Person>,
 }
 impl DimensionalPrefixes {
     pub fn all_false() -> Self {
@@ -1068,6 +1414,7 @@ impl DimensionalPrefixes {
             locative: None,
             ablative: false,
             terminative: false,
+            initial_person: None,
         }
     }
     pub fn with_comitative() -> Self {
@@ -1077,6 +1424,18 @@ impl DimensionalPrefixes {
             locative: None,
             ablative: false,
             terminative: false,
+            initial_person: None,
+        }
+    }
+    // the initial person-prefix is placed before the dimensional prefix
+    pub fn with_comitative_person(initial_person: Person) -> Self {
+        DimensionalPrefixes {
+            indirect_object: None,
+            comitative: true,
+            locative: None,
+            ablative: false,
+            terminative: false,
+            initial_person: Some(initial_person),
         }
     }
     pub fn with_locative(prefix: LocativePrefix) -> Self {
@@ -1086,6 +1445,7 @@ impl DimensionalPrefixes {
             locative: Some(prefix),
             ablative: false,
             terminative: false,
+            initial_person: None,
         }
     }
     pub fn with_ablative() -> Self {
@@ -1095,6 +1455,17 @@ impl DimensionalPrefixes {
             locative: None,
             ablative: true,
             terminative: false,
+            initial_person: None,
+        }
+    }
+    pub fn with_ablative_person(initial_person: Person) -> Self {
+        DimensionalPrefixes {
+            indirect_object: None,
+            comitative: false,
+            locative: None,
+            ablative: true,
+            terminative: false,
+            initial_person: Some(initial_person),
         }
     }
     pub fn with_terminative() -> Self {
@@ -1104,6 +1475,17 @@ impl DimensionalPrefixes {
             locative: None,
             ablative: false,
             terminative: true,
+            initial_person: None,
+        }
+    }
+    pub fn with_terminative_person(initial_person: Person) -> Self {
+        DimensionalPrefixes {
+            indirect_object: None,
+            comitative: false,
+            locative: None,
+            ablative: false,
+            terminative: true,
+            initial_person: Some(initial_person),
         }
     }
     pub fn with_indirect_object(indirect_object: Person) -> Self {
@@ -1113,6 +1495,7 @@ impl DimensionalPrefixes {
             locative: None,
             ablative: false,
             terminative: false,
+            initial_person: None,
         }
     }
 }
@@ -0,0 +1,152 @@
+use crate::lexicon::{LexiconEntry, VerbForm};
+use crate::{
+    ConjugatedForm, ConjugationError, DimensionalPrefixes, FiniteVerbalForm, MarkerName, Person,
+    PERSONS,
+};
+
+/*
+    A paradigm crosses every subject person with both aspects and, unless the verb
+    comes from the lexicon, both transitivities. The object persons and a selection
+    of dimensional prefixes can be added to it. Each combination is a row holding
+    the configuration and the printed form, or the error returned by print.
+*/
+
+#[derive(Debug, Clone)]
+pub struct ParadigmRow {
+    pub stem: String,
+    pub is_perfective: bool,
+    pub is_transitive: bool,
+    pub subject: Person,
+    pub object: Option<Person>,
+    pub dimensional_prefixes: Option<DimensionalPrefixes>,
+    pub form: Result<ConjugatedForm, ConjugationError>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Paradigm {
+    pub stem: String,
+    pub lexeme: Option<LexiconEntry>,
+    pub with_objects: bool,
+    pub dimensional_prefixes: Vec<DimensionalPrefixes>,
+}
+impl Paradigm {
+    pub fn from_stem(stem: String) -> Self {
+        Paradigm {
+            stem,
+            lexeme: None,
+            with_objects: false,
+            dimensional_prefixes: Vec::new(),
+        }
+    }
+    pub fn from_verb_form(verb_form: &VerbForm<'_>) -> Self {
+        Paradigm::from_lexicon_entry(&verb_form.into())
+    }
+    // the stems and the transitivity are taken from the lexicon entry
+    pub fn from_lexicon_entry(lexeme: &LexiconEntry) -> Self {
+        Paradigm {
+            stem: lexeme.stem.clone(),
+            lexeme: Some(lexeme.clone()),
+            with_objects: false,
+            dimensional_prefixes: Vec::new(),
+        }
+    }
+    pub fn with_objects(&mut self) -> &mut Self {
+        self.with_objects = true;
+        self
+    }
+    pub fn add_dimensional_prefixes(&mut self, prefixes: DimensionalPrefixes) -> &mut Self {
+        self.dimensional_prefixes.push(prefixes);
+        self
+    }
+
+    pub fn rows(&self) -> Vec<ParadigmRow> {
+        let transitivities = match &self.lexeme {
+            Some(lexeme) => vec![lexeme.transitive],
+            None => vec![false, true],
+        };
+        let dimensional_prefixes: Vec<Option<DimensionalPrefixes>> = [None]
+            .into_iter()
+            .chain(self.dimensional_prefixes.iter().cloned().map(Some))
+            .collect();
+
+        let mut rows = Vec::new();
+        for is_perfective in [true, false] {
+            for &is_transitive in transitivities.iter() {
+                for subject in PERSONS {
+                    for object in self.objects(is_transitive) {
+                        for prefixes in dimensional_prefixes.iter() {
+                            rows.push(self.row(
+                                is_perfective,
+                                is_transitive,
+                                subject.clone(),
+                                object.clone(),
+                                prefixes.clone(),
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+        rows
+    }
+
+    fn objects(&self, is_transitive: bool) -> Vec<Option<Person>> {
+        match is_transitive && self.with_objects {
+            true => PERSONS.into_iter().map(Some).collect(),
+            false => vec![None],
+        }
+    }
+
+    fn row(
+        &self,
+        is_perfective: bool,
+        is_transitive: bool,
+        subject: Person,
+        object: Option<Person>,
+        dimensional_prefixes: Option<DimensionalPrefixes>,
+    ) -> ParadigmRow {
+        let mut verb = match &self.lexeme {
+            Some(lexeme) => FiniteVerbalForm::from_lexicon_entry(lexeme),
+            None => FiniteVerbalForm::from_stem(self.stem.clone()),
+        };
+        match is_perfective {
+            true => verb.is_perfective(),
+            false => verb.is_imperfective(None),
+        };
+        match is_transitive {
+            true => verb.is_transitive(),
+            false => verb.is_intransitive(),
+        };
+        verb.set_subject(subject.clone());
+        let form = match &dimensional_prefixes {
+            Some(prefixes) => verb.set_dimensional_prefixes(prefixes).map(|_| ()),
+            None => Ok(()),
+        }
+        .and_then(|_| match object.clone() {
+            Some(object) => verb
+                .set_object(object)
+                .and_then(|verb| verb.print_segmented()),
+            None => verb.print_segmented(),
+        });
+        // the stem of the lexicon entry is chosen when the verb is printed
+        let stem = match &form {
+            Ok(form) => form
+                .morphs
+                .iter()
+                .find(|morph| matches!(morph.slot, MarkerName::Stem))
+                .map(|morph| morph.morpheme.clone())
+                .unwrap_or_else(|| self.stem.clone()),
+            Err(_) => self.stem.clone(),
+        };
+
+        ParadigmRow {
+            stem,
+            is_perfective,
+            is_transitive,
+            subject,
+            object,
+            dimensional_prefixes,
+            form,
+        }
+    }
+}
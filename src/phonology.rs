@@ -0,0 +1,354 @@
+use crate::utils::{ends_with_consonant, ends_with_vowel};
+use crate::{
+    FinalPersonPrefix, FinalVerbImpl, FiniteVerbalForm, FirstPrefix, IndirectObjectPrefix,
+    InitialPersonPrefix, LocativePrefix, MarkerName, Preformative, RuleApplication, SlotChange,
+};
+
+/*
+    Every rule updates the morphems of the final verb according to their phonologic environment.
+    A rule is only applied if its condition is met,
+    the rules are applied in the order in which they appear in RULES.
+*/
+
+pub struct PhonologicalRule {
+    pub section: Option<&'static str>, // section of the grammar describing the rule
+    pub description: &'static str,
+    pub condition: fn(&FiniteVerbalForm, &[String; 15]) -> bool,
+    pub rewrite: fn(&FiniteVerbalForm, &mut [String; 15]),
+}
+
+pub static RULES: [PhonologicalRule; 16] = [
+    PhonologicalRule {
+        section: None,
+        description: "the {ed} marker loses its vowel after a stem ending in a vowel",
+        condition: |verb, _| verb.slot_13.is_some() && ends_with_vowel(&verb.slot_12),
+        rewrite: |_, final_verb| final_verb.add_ed_marker("d".to_string()),
+    },
+    PhonologicalRule {
+        // 13.2.4 The prefix {e} contracts with a preceding vowel, lengthening that vowel
+        section: Some("13.2.4"),
+        description: "the final person-prefix {e} contracts with a preceding vowel",
+        condition: |verb, final_verb| {
+            matches!(
+                verb.slot_11,
+                Some(FinalPersonPrefix::SecondSingHuman | FinalPersonPrefix::SecondPlurHuman)
+            ) && final_verb
+                .find_previous_morphem(10)
+                .and_then(|morphem| contracting_vowel(&morphem))
+                .is_some()
+        },
+        rewrite: |_, final_verb| {
+            if let Some(vowel) = final_verb
+                .find_previous_morphem(10)
+                .and_then(|morphem| contracting_vowel(&morphem))
+            {
+                final_verb.add_final_ps_prefix(vowel);
+            }
+        },
+    },
+    PhonologicalRule {
+        // 16.2.5 In the texts of our corpus, the ventive prefix {mu} (chapter 17)
+        // is always used before the initial person-prefix /ʔ/ and always has the form /mu/
+        section: Some("16.2.5"),
+        description: "the ventive prefix {mu} is used before the initial person-prefix /ʔ/",
+        condition: |verb, _| matches!(verb.slot_6, Some(InitialPersonPrefix::FirstSing)),
+        rewrite: |_, final_verb| final_verb.add_ventive("mu".to_string()),
+    },
+    PhonologicalRule {
+        // 21.2 Only after the ventive prefix (§22.2), {ba} has a slightly different form,
+        // because the /b/ of {ba} assimilates to the preceding /m/ of the ventive.
+        section: Some("21.2"),
+        description: "the /b/ of the middle prefix {ba} assimilates to the /m/ of the ventive",
+        condition: |verb, _| verb.slot_4.is_some() && verb.slot_5.is_some(),
+        rewrite: |_, final_verb| {
+            final_verb.add_ventive(String::from("m"));
+            final_verb.add_middle_prefix("ma".to_string());
+        },
+    },
+    PhonologicalRule {
+        // 17.2.1 After the ventive prefix (§22.2), the prefix {ba} has a slighly different form,
+        // because the /b/ of {ba} assimilates to the preceding /m/ of the ventive:
+        section: Some("17.2.1"),
+        description: "the /b/ of the dative prefix {ba} assimilates to the /m/ of the ventive",
+        condition: |verb, _| {
+            verb.slot_4.is_some()
+                && matches!(
+                    verb.slot_7,
+                    Some(
+                        IndirectObjectPrefix::ThirdSingNonHuman
+                            | IndirectObjectPrefix::ThirdPlurNonHuman
+                    )
+                )
+        },
+        rewrite: |_, final_verb| {
+            final_verb.add_ventive(String::from("m"));
+            final_verb.add_indirect_object(String::from("ma"));
+        },
+    },
+    PhonologicalRule {
+        // If the verbal form begins with the vocalic prefix /ʔi/ (§24.3),
+        // /ḫa/ contracts with it. The sequence /ḫaʔi/ thus becomes /ḫē/
+        section: Some("24.3"),
+        description: "the modal prefix /ḫa/ contracts with the vocalic prefix /ʔi/",
+        condition: |verb, _| {
+            matches!(verb.slot_1, Some(FirstPrefix::Modal))
+                && matches!(verb.slot_2, Some(Preformative::I))
+                && verb.slot_3.is_none()
+        },
+        rewrite: |_, final_verb| {
+            final_verb.add_preformative_prefix("".to_string());
+            final_verb.add_modal_prefix(Some("ḫē".to_string()));
+        },
+    },
+    PhonologicalRule {
+        // TODO: 24.3.1 they are never found before a prefix with the shape /CV/.
+        // Instead of a vocalic prefix we then find zero, that is, no preformative at all.
+        // 24.3.2 The prefix {ʔi} may also contract with the verbal stem,
+        // if the latter has an initial glottal stop.
+        section: Some("24.3"),
+        description: "the vocalic prefix assimilates to a neighbouring /u/",
+        condition: |verb, final_verb| {
+            if verb.slot_2.is_none() || matches!(verb.slot_1, Some(FirstPrefix::Modal)) {
+                return false;
+            }
+            match final_verb.find_previous_morphem(1) {
+                // looks for previous morphem, if any
+                Some(morphem) => morphem.ends_with("u"),
+                // if no previous morphem, looks for the next morphem
+                // and checks if it is the stem
+                None => match final_verb.find_following_morphem(2) {
+                    Some((morphem, morphem_name)) => {
+                        morphem.starts_with("u") && morphem_name == MarkerName::Stem
+                    }
+                    None => false,
+                },
+            }
+        },
+        rewrite: |_, final_verb| final_verb.add_preformative_prefix("u".to_string()),
+    },
+    PhonologicalRule {
+        // 24.3.1 The vocalic prefixes are never found before a prefix with the shape /CV/,
+        // so that {nga} directly follows {nu} or {ḫa}: /nunga/, /ḫanga/ (and not /ḫēnga/)
+        section: Some("24.3.1"),
+        description: "the vocalic prefix is zero before the coordinator prefix {nga}",
+        condition: |verb, _| {
+            verb.slot_3.is_some() && verb.slot_2.is_some() && verb.slot_1.is_some()
+        },
+        rewrite: |_, final_verb| final_verb.add_preformative_prefix("".to_string()),
+    },
+    PhonologicalRule {
+        // 23 The coordinator prefix {nga} never begins a verbal form,
+        // it is then preceded by the vocalic prefix /ʔi/
+        section: Some("23"),
+        description: "the coordinator prefix {nga} is preceded by the vocalic prefix /ʔi/",
+        condition: |verb, _| {
+            verb.slot_3.is_some() && verb.slot_2.is_none() && verb.slot_1.is_none()
+        },
+        rewrite: |_, final_verb| final_verb.add_preformative_prefix("i".to_string()),
+    },
+    PhonologicalRule {
+        // 14.1 First, the /e/ contracts with a preceding vowel.
+        // Secondly, the /e/ may assimilate to a stem vowel /u/ or /i/.
+        // FIXME: leaving "e" untouched for now
+        section: Some("14.1"),
+        description: "the /e/ of the person-suffix contracts with a preceding vowel",
+        condition: |verb, final_verb| {
+            if verb.slot_14.is_none() {
+                return false;
+            }
+            match (
+                final_verb.find_previous_morphem(12),
+                final_verb.find_final_ps_suffix(),
+            ) {
+                (Some(morphem), Some(suffix)) => {
+                    suffix.len() > 1
+                        && (morphem.ends_with("a")
+                            || morphem.ends_with("i")
+                            || morphem.ends_with("u")
+                            || morphem.ends_with("e"))
+                }
+                _ => false,
+            }
+        },
+        rewrite: |_, final_verb| {
+            if let Some(mut truncated_suffix) = final_verb.find_final_ps_suffix() {
+                truncated_suffix.remove(0);
+                final_verb.add_final_ps_suffix(truncated_suffix);
+            }
+        },
+    },
+    PhonologicalRule {
+        // 14.1 Finally, the /e/ may be reduced in forms with the nominalizing suffix {ʔa}
+        section: Some("14.1"),
+        description: "the /e/ of the person-suffix is reduced before the nominalizing suffix {ʔa}",
+        condition: |verb, final_verb| {
+            verb.slot_15 && final_verb.find_final_ps_suffix() == Some("e".to_string())
+        },
+        rewrite: |_, final_verb| final_verb.add_final_ps_suffix("".to_string()),
+    },
+    PhonologicalRule {
+        // The glottal stop of {ʔa} assimilates to a preceding consonant,
+        // be it the final consonant of the stem, of the {ed} marker or of a person suffix
+        section: None,
+        description: "the glottal stop of {ʔa} assimilates to a preceding consonant",
+        condition: |verb, final_verb| {
+            verb.slot_15
+                && final_verb
+                    .find_previous_morphem(14)
+                    .is_some_and(|morphem| ends_with_consonant(&morphem))
+        },
+        rewrite: |_, final_verb| {
+            if let Some(consonant) = final_verb
+                .find_previous_morphem(14)
+                .and_then(|morphem| morphem.chars().last())
+            {
+                final_verb.add_subordinator(format!("{}a", consonant));
+            }
+        },
+    },
+    PhonologicalRule {
+        // First, the prefix {b} cannot occur between the ventive prefix and a consonant (see §22.4).
+        // Second, between the form /m/ of the ventive and a vowel, the prefix {b} assimilates to the /m/.
+        section: Some("22.4"),
+        description: "the prefix {b} is lost or assimilates after the ventive",
+        condition: |verb, _| {
+            matches!(verb.slot_6, Some(InitialPersonPrefix::ThirdSingNonHuman))
+                && verb.slot_4.is_some()
+                // no change with a dative marker
+                && verb.slot_7.is_none()
+                && (verb.slot_8.is_some() || verb.slot_9.is_some() || verb.slot_10.is_some())
+        },
+        rewrite: |verb, final_verb| {
+            final_verb.add_initial_person_prefix("".to_string());
+            final_verb.add_ventive(String::from("m"));
+            if verb.slot_8.is_none() && verb.slot_9.is_none() {
+                if let Some(LocativePrefix::OnWithInitialPerson) = verb.slot_10 {
+                    // the /b/ of /bi/ assimilates to the /m/ of the ventive
+                    final_verb.add_locative_prefix("mi".to_string());
+                }
+            }
+        },
+    },
+    PhonologicalRule {
+        // 16.2.4 The prefix {e} contracts with a preceding vowel, lengthening that vowel.
+        section: Some("16.2.4"),
+        description: "the initial person-prefix {e} contracts with a preceding vowel",
+        condition: |verb, final_verb| {
+            matches!(verb.slot_6, Some(InitialPersonPrefix::SecondSing))
+                && final_verb
+                    .find_previous_morphem(5)
+                    .and_then(|morphem| contracting_vowel(&morphem))
+                    .is_some()
+        },
+        rewrite: |_, final_verb| {
+            if let Some(vowel) = final_verb
+                .find_previous_morphem(5)
+                .and_then(|morphem| contracting_vowel(&morphem))
+            {
+                final_verb.add_initial_person_prefix(vowel);
+            }
+        },
+    },
+    PhonologicalRule {
+        section: Some("20.1"),
+        description: "the form of the locative prefix depends on the final person-prefix",
+        condition: |verb, _| {
+            matches!(
+                verb.slot_10,
+                Some(
+                    LocativePrefix::InWithoutInitialPerson | LocativePrefix::OnWithoutInitialPerson
+                )
+            )
+        },
+        rewrite: |verb, final_verb| {
+            let prefix_output = match (&verb.slot_10, &verb.slot_11) {
+                (Some(LocativePrefix::InWithoutInitialPerson), Some(_)) => "ni",
+                (Some(LocativePrefix::InWithoutInitialPerson), None) => "n",
+                (Some(LocativePrefix::OnWithoutInitialPerson), Some(_)) => "",
+                _ => "e",
+            };
+            final_verb.add_locative_prefix(prefix_output.to_string());
+        },
+    },
+    PhonologicalRule {
+        // 22.2 Before the indirect-object prefix {ra}, the oblique-object prefix {ri},
+        // and the local prefix {ni}, however, the /u/ is always retained
+        // but may assimilate to the vowel of the following syllable.
+        section: Some("22.2"),
+        description: "the /u/ of the ventive assimilates to the vowel of the following syllable",
+        condition: |verb, final_verb| {
+            verb.slot_4.is_some() && ventive_assimilation(final_verb).is_some()
+        },
+        rewrite: |_, final_verb| {
+            if let Some(ventive) = ventive_assimilation(final_verb) {
+                final_verb.add_ventive(ventive);
+            }
+        },
+    },
+];
+
+// vowel resulting from the contraction of {e} with the preceding morphem
+fn contracting_vowel(morphem: &str) -> Option<String> {
+    if morphem.ends_with("a") {
+        Some(String::from("a"))
+    } else if morphem.ends_with("i") {
+        Some(String::from("i"))
+    } else if morphem.ends_with("u") {
+        Some(String::from("u"))
+    } else {
+        None
+    }
+}
+
+fn ventive_assimilation(final_verb: &[String; 15]) -> Option<String> {
+    match final_verb.find_following_morphem(MarkerName::Ventive.position() + 1) {
+        Some((morphem, MarkerName::LocativePrefix)) if morphem == "ni" || morphem == "ri" => {
+            Some(String::from("mi"))
+        }
+        Some((morphem, MarkerName::DativePrefix)) if morphem == "ra" => Some(String::from("ma")),
+        _ => None,
+    }
+}
+
+pub fn apply_rules(
+    verb: &FiniteVerbalForm,
+    final_verb: &mut [String; 15],
+    trace: &mut Vec<RuleApplication>,
+) {
+    for rule in RULES.iter() {
+        if (rule.condition)(verb, final_verb) {
+            let before = final_verb.clone();
+            (rule.rewrite)(verb, final_verb);
+            record_rule(trace, rule, &before, final_verb);
+        }
+    }
+}
+
+// Adds the rule to the trace if it modified at least one slot
+fn record_rule(
+    trace: &mut Vec<RuleApplication>,
+    rule: &PhonologicalRule,
+    before: &[String; 15],
+    after: &[String; 15],
+) {
+    let changes: Vec<SlotChange> = before
+        .iter()
+        .zip(after.iter())
+        .enumerate()
+        .filter(|(_, (before, after))| before != after)
+        .filter_map(|(i, (before, after))| {
+            <[String; 15]>::name_by_position(i).map(|slot| SlotChange {
+                slot,
+                before: before.clone(),
+                after: after.clone(),
+            })
+        })
+        .collect();
+    if !changes.is_empty() {
+        trace.push(RuleApplication {
+            section: rule.section.map(|section| section.to_string()),
+            description: rule.description.to_string(),
+            changes,
+        });
+    }
+}
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Deserializer, Serializer};
+
+/*
+    The slots holding a marker without any form of its own (coordinator, ventive,
+    middle and comitative prefixes) are serialized as true or false.
+*/
+
+pub fn serialize<T, S: Serializer>(slot: &Option<T>, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_bool(slot.is_some())
+}
+
+pub fn deserialize<'de, T: Default, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<T>, D::Error> {
+    Ok(bool::deserialize(deserializer)?.then(T::default))
+}
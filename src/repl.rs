@@ -0,0 +1,253 @@
+use crate::cli::{apply_command, find_command, COMMANDS};
+use crate::{ConjugationError, FiniteVerbalForm, FirstPrefix, IpfvStem};
+use std::fmt::Write;
+
+/*
+    Interactive session building a verb step by step. Every command of the command line
+    is available without its dashes (subject 3sg.h, ventive...): the markers without
+    argument are toggled, "none" clears the slots set by a command, and the verb is
+    printed again after each change.
+*/
+
+pub struct SavedForm {
+    pub surface: String,
+    pub hyphenated: String,
+    pub gloss: String,
+    pub verb: FiniteVerbalForm,
+}
+
+#[derive(Default)]
+pub struct Session {
+    pub verb: Option<FiniteVerbalForm>,
+    pub history: Vec<Option<FiniteVerbalForm>>,
+    pub saved: Vec<SavedForm>,
+    pub finished: bool,
+}
+
+impl Session {
+    pub fn from_verb(verb: FiniteVerbalForm) -> Self {
+        Session {
+            verb: Some(verb),
+            ..Session::default()
+        }
+    }
+
+    // Runs a line typed by the user and returns what has to be shown
+    pub fn execute(&mut self, line: &str) -> Result<String, ConjugationError> {
+        let mut words = line.split_whitespace();
+        let Some(name) = words.next() else {
+            return Ok(String::new());
+        };
+        let argument = words.next();
+
+        match (name, argument) {
+            ("help", _) => Ok(help()),
+            ("quit" | "exit", _) => {
+                self.finished = true;
+                Ok(String::new())
+            }
+            ("stem", Some(stem)) => {
+                self.change(|_| Ok(FiniteVerbalForm::from_stem(stem.to_string())))
+            }
+            ("lexeme", Some(stem)) => self.change(|_| FiniteVerbalForm::from_lexeme(stem)),
+            ("undo", None) => match self.history.pop() {
+                Some(verb) => {
+                    self.verb = verb;
+                    self.render()
+                }
+                None => Err(ConjugationError::InvalidSpecification(
+                    "nothing to undo".to_string(),
+                )),
+            },
+            ("show", None) => self.render(),
+            ("save", None) => {
+                let mut verb = self.current()?.clone();
+                let form = verb.print_segmented()?;
+                self.saved.push(SavedForm {
+                    surface: form.surface(),
+                    hyphenated: form.hyphenated(),
+                    gloss: form.gloss(),
+                    verb,
+                });
+                Ok(format!("saved {}", form.surface()))
+            }
+            ("saved", None) => Ok(self.list_saved()),
+            ("write", Some(path)) => {
+                std::fs::write(path, self.list_saved())
+                    .map_err(|err| ConjugationError::InvalidSpecification(err.to_string()))?;
+                Ok(format!("{} forms written to {}", self.saved.len(), path))
+            }
+            (name, Some("none")) => self.change(|verb| {
+                let mut verb = verb?;
+                clear_command(&mut verb, name)?;
+                Ok(verb)
+            }),
+            (name, argument) => self.change(|verb| {
+                let mut verb = verb?;
+                if argument.is_none() && is_set(&verb, name) {
+                    clear_command(&mut verb, name)?;
+                } else {
+                    apply_command(&mut verb, name, argument)?;
+                }
+                Ok(verb)
+            }),
+        }
+    }
+
+    fn current(&self) -> Result<&FiniteVerbalForm, ConjugationError> {
+        self.verb
+            .as_ref()
+            .ok_or(ConjugationError::InvalidSpecification(
+                "no verb yet, start with 'stem STEM' or 'lexeme STEM'".to_string(),
+            ))
+    }
+
+    // Replaces the verb if the change succeeds, the previous one can then be restored with undo
+    fn change(
+        &mut self,
+        change: impl FnOnce(
+            Result<FiniteVerbalForm, ConjugationError>,
+        ) -> Result<FiniteVerbalForm, ConjugationError>,
+    ) -> Result<String, ConjugationError> {
+        let verb = change(self.current().cloned())?;
+        self.history.push(self.verb.replace(verb));
+        self.render()
+    }
+
+    // e.g. "munzu  mu-n-zu  VEN-3SG.H.A-know", or the reason why the verb cannot be printed
+    pub fn render(&self) -> Result<String, ConjugationError> {
+        let mut verb = self.current()?.clone();
+        Ok(match verb.print_segmented() {
+            Ok(form) => format!(
+                "{}  {}  {}",
+                form.surface(),
+                form.hyphenated(),
+                form.gloss()
+            ),
+            Err(error) => error.to_string(),
+        })
+    }
+
+    fn list_saved(&self) -> String {
+        let mut list = String::new();
+        for (i, form) in self.saved.iter().enumerate() {
+            let _ = writeln!(
+                list,
+                "{}. {}  {}  {}",
+                i + 1,
+                form.surface,
+                form.hyphenated,
+                form.gloss
+            );
+        }
+        list
+    }
+}
+
+// the markers without argument set with the command
+fn is_set(verb: &FiniteVerbalForm, name: &str) -> bool {
+    match name {
+        "negative" => matches!(verb.slot_1, Some(FirstPrefix::Negative)),
+        "modal" => matches!(verb.slot_1, Some(FirstPrefix::Modal)),
+        "coordinator" => verb.slot_3.is_some(),
+        "ventive" => verb.slot_4.is_some(),
+        "middle" => verb.slot_5.is_some(),
+        "subordinator" => verb.slot_15,
+        "ed" => {
+            matches!(verb.imperfective_stem, Some(IpfvStem::EdMarker)) || verb.slot_13.is_some()
+        }
+        _ => false,
+    }
+}
+
+// Empties the slots set by the command
+fn clear_command(verb: &mut FiniteVerbalForm, name: &str) -> Result<(), ConjugationError> {
+    match find_command(name)?.name {
+        // the other prefix of the slot 1 is kept
+        name @ ("negative" | "modal") => {
+            if is_set(verb, name) {
+                verb.slot_1 = None;
+            }
+        }
+        "coordinator" => {
+            verb.set_coordinator(None);
+        }
+        "ventive" => verb.slot_4 = None,
+        "middle" => {
+            verb.set_middle_prefix(None);
+        }
+        "subordinator" => {
+            verb.set_subordinator(false);
+        }
+        "subject" => verb.subject = None,
+        "object" => verb.object = None,
+        "oblique" | "locative" => {
+            verb.set_oblique_object(None);
+        }
+        "preformative" => {
+            verb.set_preformative(None);
+        }
+        "initial-person" => {
+            verb.set_initial_person_prefix(None);
+        }
+        "dative" => verb.slot_7 = None,
+        // the initial person-prefix is kept for the other dimensional prefixes
+        "comitative" => {
+            verb.slot_8 = None;
+            if !verb.uses_initial_person() {
+                verb.set_initial_person_prefix(None);
+            }
+        }
+        "ablative" | "terminative" => {
+            verb.set_adverbial_prefix(None);
+            if !verb.uses_initial_person() {
+                verb.set_initial_person_prefix(None);
+            }
+        }
+        "final-person-prefix" => {
+            verb.set_final_person_prefix(None);
+        }
+        "final-person-suffix" => {
+            verb.set_final_person_suffix(None);
+        }
+        "ed" => {
+            if matches!(verb.imperfective_stem, Some(IpfvStem::EdMarker)) {
+                verb.imperfective_stem = None;
+            }
+            verb.set_ed_marker(None);
+        }
+        _ => {
+            return Err(ConjugationError::InvalidSpecification(format!(
+                "'{}' cannot be cleared",
+                name
+            )))
+        }
+    }
+    Ok(())
+}
+
+fn help() -> String {
+    let mut help = String::from(
+        "stem STEM  start a new verb\n\
+         lexeme STEM  start a new verb from the lexicon\n\
+         undo  come back to the previous verb\n\
+         show  print the verb\n\
+         save  keep the current form\n\
+         saved  list the saved forms\n\
+         write PATH  write the saved forms to a file\n\
+         quit  leave the session\n\n\
+         COMMAND none  clear the slots set by the command\n",
+    );
+    for command in COMMANDS.iter() {
+        let argument = command
+            .argument
+            .map(|argument| format!(" {}", argument))
+            .unwrap_or_default();
+        let _ = writeln!(
+            help,
+            "{}{}  {}",
+            command.name, argument, command.description
+        );
+    }
+    help
+}
@@ -3,7 +3,7 @@ mod tests {
     use crate::*;
 
     #[test]
-    fn ak() -> Result<(), String> {
+    fn ak() -> Result<(), ConjugationError> {
         let stem = "ak".to_string();
 
         let verb = FiniteVerbalForm::from_stem(stem.clone())
@@ -33,6 +33,75 @@ mod tests {
             .set_terminative(Some(Person::ThirdSingNonHuman))
             .print();
         assert_eq!(Ok(String::from("ibšinak")), verb);
+        // They made it 13.3.1
+        let verb = FiniteVerbalForm::from_stem(stem.clone())
+            .is_perfective()
+            .is_transitive()
+            .set_subject(Person::ThirdPlurHuman)
+            .set_object(Person::ThirdSingNonHuman)?
+            .set_preformative(Some(Preformative::I))
+            .print();
+        assert_eq!(Ok(String::from("inakeš")), verb);
+        // He also made it 23, {nga} is preceded by the vocalic prefix /ʔi/
+        let verb = FiniteVerbalForm::from_stem(stem.clone())
+            .is_perfective()
+            .is_transitive()
+            .set_subject(Person::ThirdSingHuman)
+            .set_object(Person::ThirdSingNonHuman)?
+            .set_coordinator(Some(Coordinator))
+            .print();
+        assert_eq!(Ok(String::from("inganak")), verb);
+        let verb = FiniteVerbalForm::from_stem(stem.clone())
+            .is_perfective()
+            .is_transitive()
+            .set_subject(Person::ThirdSingHuman)
+            .set_object(Person::ThirdSingNonHuman)?
+            .set_coordinator(Some(Coordinator))
+            .print_segmented()?;
+        assert_eq!("i-nga-n-ak", verb.hyphenated());
+        assert_eq!("FIN-COOR-3SG.H.A-ak", verb.gloss());
+        // He did not make it either 24.3.1, no vocalic prefix between {nu} and {nga}
+        let verb = FiniteVerbalForm::from_stem(stem.clone())
+            .is_perfective()
+            .is_transitive()
+            .set_subject(Person::ThirdSingHuman)
+            .set_object(Person::ThirdSingNonHuman)?
+            .set_coordinator(Some(Coordinator))
+            .set_negative()
+            .print();
+        assert_eq!(Ok(String::from("nunganak")), verb);
+        // May he also make it 24.3.1, the vocalic prefix is zero before the /CV/ prefix {nga},
+        // so that {ḫa} does not contract with it into /ḫē/ (24.3)
+        let verb = FiniteVerbalForm::from_stem(stem.clone())
+            .is_perfective()
+            .is_transitive()
+            .set_subject(Person::ThirdSingHuman)
+            .set_object(Person::ThirdSingNonHuman)?
+            .set_coordinator(Some(Coordinator))
+            .set_preformative(Some(Preformative::I))
+            .set_modal()
+            .print_segmented()?;
+        assert_eq!("ḫanganak", verb.surface());
+        assert_eq!("ḫa-nga-n-ak", verb.hyphenated());
+        let verb = FiniteVerbalForm::from_stem(stem.clone())
+            .is_perfective()
+            .is_transitive()
+            .set_subject(Person::ThirdSingHuman)
+            .set_object(Person::ThirdSingNonHuman)?
+            .set_coordinator(Some(Coordinator))
+            .set_preformative(Some(Preformative::I))
+            .set_negative()
+            .print();
+        assert_eq!(Ok(String::from("nunganak")), verb);
+        let mut form = FiniteVerbalForm::from_stem(stem.clone());
+        form.is_perfective()
+            .is_transitive()
+            .set_subject(Person::ThirdPlurHuman)
+            .set_object(Person::ThirdSingNonHuman)?;
+        assert!(matches!(
+            form.resolve_arguments()?.slot_11,
+            Some(FinalPersonPrefix::ThirdPlurHuman)
+        ));
 
         match verb {
             Err(err) => {
@@ -43,7 +112,7 @@ mod tests {
     }
 
     #[test]
-    fn bala() -> Result<(), String> {
+    fn bala() -> Result<(), ConjugationError> {
         // I cross it
         let stem = "bala".to_string();
         let verb = FiniteVerbalForm::from_stem(stem.clone())
@@ -64,7 +133,7 @@ mod tests {
     }
 
     #[test]
-    fn daḫ() -> Result<(), String> {
+    fn daḫ() -> Result<(), ConjugationError> {
         let stem = "daḫ".to_string();
         // I shall add for you 17.2.4 (33)
         let verb = FiniteVerbalForm::from_stem(stem.clone())
@@ -85,7 +154,7 @@ mod tests {
     }
 
     #[test]
-    fn e() -> Result<(), String> {
+    fn e() -> Result<(), ConjugationError> {
         // May he say it to him! 25.4.1 (49)
         let stem = "ʔe".to_string();
         let verb = FiniteVerbalForm::from_stem(stem.clone())
@@ -109,7 +178,7 @@ mod tests {
     }
 
     #[test]
-    fn è() -> Result<(), String> {
+    fn è() -> Result<(), ConjugationError> {
         // He must let it come out of him 16.2.2 (17)
         let stem = "ʔè".to_string();
         let verb = FiniteVerbalForm::from_stem(stem.clone())
@@ -133,7 +202,7 @@ mod tests {
     }
 
     #[test]
-    fn ĝal() -> Result<(), String> {
+    fn ĝal() -> Result<(), ConjugationError> {
         let stem = "ĝal".to_string();
         // This is with him 16.2.2 (14a)
         let verb = FiniteVerbalForm::from_stem(stem.clone())
@@ -154,7 +223,7 @@ mod tests {
     }
 
     #[test]
-    fn ĝar() -> Result<(), String> {
+    fn ĝar() -> Result<(), ConjugationError> {
         // He placed it from it 16.2.1 (7)
         let stem = "ĝar".to_string();
         let verb = FiniteVerbalForm::from_stem(stem.clone())
@@ -166,6 +235,28 @@ mod tests {
             .set_preformative(Some(Preformative::I))
             .print();
         assert_eq!(Ok(String::from("ibtanĝar")), verb);
+        // He placed it on you 13.1
+        let verb = FiniteVerbalForm::from_stem(stem.clone())
+            .is_perfective()
+            .is_transitive()
+            .set_subject(Person::ThirdSingHuman)
+            .set_object(Person::ThirdSingNonHuman)?
+            .set_oblique_object(Some(Person::SecondSing))
+            .set_ventive()
+            .print();
+        assert_eq!(Ok(String::from("mirinĝar")), verb);
+        // He placed it there towards me 22.4
+        let verb = FiniteVerbalForm::from_stem(stem.clone())
+            .is_perfective()
+            .is_transitive()
+            .set_subject(Person::ThirdSingHuman)
+            .set_object(Person::ThirdSingNonHuman)?
+            .set_initial_person_prefix(Some(Person::ThirdSingNonHuman))
+            .set_locative_on(Some(Person::ThirdSingNonHuman))
+            .set_ventive()
+            .set_preformative(Some(Preformative::I))
+            .print();
+        assert_eq!(Ok(String::from("imminĝar")), verb);
         // They were placed on it 21.2 (7)
         // let verb = FiniteVerbalForm::from_stem(stem.clone())
         //     .is_perfective()
@@ -192,7 +283,7 @@ mod tests {
     }
 
     #[test]
-    fn ĝen() -> Result<(), String> {
+    fn ĝen() -> Result<(), ConjugationError> {
         let stem = "ĝen".to_string();
         // He came for him 22.6 (68a)
         let verb = FiniteVerbalForm::from_stem(stem.clone())
@@ -213,6 +304,15 @@ mod tests {
             .set_preformative(Some(Preformative::I))
             .print();
         assert_eq!(Ok(String::from("imšiĝen")), verb);
+        // I who went, the person-suffix with the nominalizing suffix {ʔa} 14.1
+        let verb = FiniteVerbalForm::from_stem(stem.clone())
+            .is_perfective()
+            .is_intransitive()
+            .set_subject(Person::FirstSing)
+            .set_preformative(Some(Preformative::I))
+            .set_subordinator(true)
+            .print();
+        assert_eq!(Ok(String::from("iĝenenna")), verb);
         // He came to it 17.2.1 (4)
         let verb = FiniteVerbalForm::from_stem(stem.clone())
             .is_perfective()
@@ -223,6 +323,15 @@ mod tests {
             .set_ventive()
             .print();
         assert_eq!(Ok(String::from("immaĝen")), verb);
+        // He went on it 13.1
+        let verb = FiniteVerbalForm::from_stem(stem.clone())
+            .is_perfective()
+            .is_intransitive()
+            .set_subject(Person::ThirdSingHuman)
+            .set_oblique_object(Some(Person::ThirdSingNonHuman))
+            .set_preformative(Some(Preformative::I))
+            .print();
+        assert_eq!(Ok(String::from("ibĝen")), verb);
 
         match verb {
             Err(err) => {
@@ -233,7 +342,7 @@ mod tests {
     }
 
     #[test]
-    fn gi() -> Result<(), String> {
+    fn gi() -> Result<(), ConjugationError> {
         let stem = "gi".to_string();
         // He should send him to me 16.2.5 (31)
         let verb = FiniteVerbalForm::from_stem(stem.clone())
@@ -245,6 +354,34 @@ mod tests {
             .set_modal()
             .print();
         assert_eq!(Ok(String::from("ḫamuʔšingi-gie")), verb);
+        // He sent them 14.1
+        let verb = FiniteVerbalForm::from_stem(stem.clone())
+            .is_perfective()
+            .is_transitive()
+            .set_subject(Person::ThirdSingHuman)
+            .set_object(Person::ThirdPlurHuman)?
+            .set_ventive()
+            .print();
+        assert_eq!(Ok(String::from("mungiš")), verb);
+        // the reduplicated stem is only used if the verb stays imperfective
+        let verb = FiniteVerbalForm::from_stem(stem.clone())
+            .is_imperfective(Some(IpfvStem::Reduplicate))
+            .is_transitive()
+            .set_subject(Person::ThirdSingHuman)
+            .set_object(Person::ThirdPlurHuman)?
+            .set_ventive()
+            .is_perfective()
+            .print();
+        assert_eq!(Ok(String::from("mungiš")), verb);
+        // He will send us 13.3.1
+        let verb = FiniteVerbalForm::from_stem(stem.clone())
+            .is_imperfective(Some(IpfvStem::Reduplicate))
+            .is_transitive()
+            .set_subject(Person::ThirdSingHuman)
+            .set_object(Person::FirstPlur)?
+            .set_ventive()
+            .print();
+        assert_eq!(Ok(String::from("muʔgi-gie")), verb);
 
         match verb {
             Err(err) => {
@@ -255,7 +392,7 @@ mod tests {
     }
 
     #[test]
-    fn gub() -> Result<(), String> {
+    fn gub() -> Result<(), ConjugationError> {
         // They were caused to stand 21.2 (6)
         let stem = "gub".to_string();
         let verb = FiniteVerbalForm::from_stem(stem.clone())
@@ -266,6 +403,15 @@ mod tests {
             .set_middle_prefix(Some(MiddlePrefix))
             .print();
         assert_eq!(Ok(String::from("bannēgub")), verb);
+        // Which was placed, the prefix {ba} 21.2 with the nominalizing suffix {ʔa}
+        let verb = FiniteVerbalForm::from_stem(stem.clone())
+            .is_perfective()
+            .is_intransitive()
+            .set_subject(Person::ThirdSingNonHuman)
+            .set_middle_prefix(Some(MiddlePrefix))
+            .set_subordinator(true)
+            .print();
+        assert_eq!(Ok(String::from("bagubba")), verb);
 
         match verb {
             Err(err) => {
@@ -276,7 +422,7 @@ mod tests {
     }
 
     #[test]
-    fn hul() -> Result<(), String> {
+    fn hul() -> Result<(), ConjugationError> {
         let stem = "hul".to_string();
         // He was happy about it 22.6 (67b)
         let verb = FiniteVerbalForm::from_stem(stem.clone())
@@ -288,6 +434,46 @@ mod tests {
             .set_ventive()
             .print();
         assert_eq!(Ok(String::from("imdahul")), verb);
+        let form = FiniteVerbalForm::from_stem(stem.clone())
+            .is_perfective()
+            .is_intransitive()
+            .set_subject(Person::ThirdSingHuman)
+            .set_comitative(Some(Person::ThirdSingNonHuman))
+            .set_preformative(Some(Preformative::I))
+            .set_ventive()
+            .print_segmented()?;
+        assert_eq!(String::from("i-m-da-hul"), form.hyphenated());
+        assert!(form.morphs.contains(&Morph {
+            slot: MarkerName::InitialPronominalPrefix,
+            morpheme: String::from("b"),
+            surface: String::new(),
+            gloss: String::from("3SG.NH"),
+        }));
+        assert!(form.morphs.contains(&Morph {
+            slot: MarkerName::Ventive,
+            morpheme: String::from("mu"),
+            surface: String::from("m"),
+            gloss: String::from("VEN"),
+        }));
+        assert_eq!(String::from("FIN-VEN-COM-hul"), form.gloss());
+        let (_, trace) = FiniteVerbalForm::from_stem(stem.clone())
+            .is_perfective()
+            .is_intransitive()
+            .set_subject(Person::ThirdSingHuman)
+            .set_comitative(Some(Person::ThirdSingNonHuman))
+            .set_preformative(Some(Preformative::I))
+            .set_ventive()
+            .print_traced()?;
+        assert_eq!(1, trace.len());
+        assert_eq!(Some(String::from("22.4")), trace[0].section);
+        assert_eq!(
+            vec![MarkerName::Ventive, MarkerName::InitialPronominalPrefix],
+            trace[0]
+                .changes
+                .iter()
+                .map(|change| change.slot.clone())
+                .collect::<Vec<MarkerName>>()
+        );
 
         match verb {
             Err(err) => {
@@ -298,7 +484,7 @@ mod tests {
     }
 
     #[test]
-    fn kar() -> Result<(), String> {
+    fn kar() -> Result<(), ConjugationError> {
         // He took it away from me 22.2 (12)
         let stem = "kar".to_string();
         let verb = FiniteVerbalForm::from_stem(stem.clone())
@@ -310,6 +496,29 @@ mod tests {
             .set_ventive()
             .print();
         assert_eq!(Ok(String::from("muʔdankar")), verb);
+        let (form, trace) = FiniteVerbalForm::from_stem(stem.clone())
+            .is_perfective()
+            .is_transitive()
+            .set_subject(Person::ThirdSingHuman)
+            .set_object(Person::ThirdSingNonHuman)?
+            .set_comitative(Some(Person::FirstSing))
+            .print_traced()?;
+        assert_eq!(String::from("muʔdankar"), form.surface());
+        assert_eq!(
+            vec![Some(String::from("16.2.5"))],
+            trace
+                .iter()
+                .map(|rule| rule.section.clone())
+                .collect::<Vec<Option<String>>>()
+        );
+        assert_eq!(
+            vec![SlotChange {
+                slot: MarkerName::Ventive,
+                before: String::new(),
+                after: String::from("mu"),
+            }],
+            trace[0].changes
+        );
         // He took her away from him 21.2 (4)
         let verb = FiniteVerbalForm::from_stem(stem.clone())
             .is_perfective()
@@ -330,7 +539,7 @@ mod tests {
     }
 
     #[test]
-    fn kuř() -> Result<(), String> {
+    fn kuř() -> Result<(), ConjugationError> {
         let stem = "kuř".to_string();
         let verb = FiniteVerbalForm::from_stem(stem.clone())
             .is_perfective()
@@ -362,7 +571,7 @@ mod tests {
     }
 
     #[test]
-    fn lá() -> Result<(), String> {
+    fn lá() -> Result<(), ConjugationError> {
         let stem = "lá".to_string();
         // It was weighed out for him 17.2.2 (8)
         let verb = FiniteVerbalForm::from_stem(stem.clone())
@@ -383,7 +592,7 @@ mod tests {
     }
 
     #[test]
-    fn me() -> Result<(), String> {
+    fn me() -> Result<(), ConjugationError> {
         let stem = "me".to_string();
         // I am not
         let verb = FiniteVerbalForm::from_stem(stem.clone())
@@ -404,7 +613,7 @@ mod tests {
     }
 
     #[test]
-    fn rig() -> Result<(), String> {
+    fn rig() -> Result<(), ConjugationError> {
         // It was cleared 21.2 (1a)
         let stem = "rig".to_string();
         let verb = FiniteVerbalForm::from_stem(stem.clone())
@@ -434,7 +643,7 @@ mod tests {
     }
 
     #[test]
-    fn řu() -> Result<(), String> {
+    fn řu() -> Result<(), ConjugationError> {
         let stem = "řu".to_string();
         // He erected them in it for him 20.1 (1a)
         let verb = FiniteVerbalForm::from_stem(stem.clone())
@@ -447,6 +656,17 @@ mod tests {
             .set_ventive()
             .print();
         assert_eq!(Ok(String::from("munnaninřu")), verb);
+        // Which he erected for him, the ventive 16.2.5 with the nominalizing suffix {ʔa}
+        let verb = FiniteVerbalForm::from_stem(stem.clone())
+            .is_perfective()
+            .is_transitive()
+            .set_subject(Person::ThirdSingHuman)
+            .set_object(Person::ThirdSingNonHuman)?
+            .set_indirect_object(Person::ThirdSingHuman)
+            .set_ventive()
+            .set_subordinator(true)
+            .print();
+        assert_eq!(Ok(String::from("munnanřuʔa")), verb);
         // He erected it on it 20.1 (1b)
         let verb = FiniteVerbalForm::from_stem(stem.clone())
             .is_perfective()
@@ -466,7 +686,7 @@ mod tests {
     }
 
     #[test]
-    fn sa() -> Result<(), String> {
+    fn sa() -> Result<(), ConjugationError> {
         // He bought her from them 16.2.3 (18)
         let stem = "sa".to_string();
         let verb = FiniteVerbalForm::from_stem(stem.clone())
@@ -487,7 +707,7 @@ mod tests {
         }
     }
     #[test]
-    fn sig_sig() -> Result<(), String> {
+    fn sig_sig() -> Result<(), ConjugationError> {
         let stem = "sig-sig".to_string();
         // He put them in it 20.1 (2a)
         let verb = FiniteVerbalForm::from_stem(stem.clone())
@@ -509,7 +729,7 @@ mod tests {
     }
 
     #[test]
-    fn sug() -> Result<(), String> {
+    fn sug() -> Result<(), ConjugationError> {
         let stem = "sug".to_string();
         // They stood for it 16.2.1 (2)
         let verb = FiniteVerbalForm::from_stem(stem.clone())
@@ -529,7 +749,7 @@ mod tests {
     }
 
     #[test]
-    fn šum() -> Result<(), String> {
+    fn šum() -> Result<(), ConjugationError> {
         // He gave to it 17.2.1 (1)
         let stem = "šum".to_string();
         let verb = FiniteVerbalForm::from_stem(stem.clone())
@@ -610,7 +830,7 @@ mod tests {
     }
 
     #[test]
-    fn ti() -> Result<(), String> {
+    fn ti() -> Result<(), ConjugationError> {
         let stem = "ti".to_string();
         // He will let it approach towards you 17.2.1 (6)
         let verb = FiniteVerbalForm::from_stem(stem.clone())
@@ -632,7 +852,7 @@ mod tests {
     }
 
     #[test]
-    fn tuku() -> Result<(), String> {
+    fn tuku() -> Result<(), ConjugationError> {
         let stem = "tuku".to_string();
         // I had it
         let verb = FiniteVerbalForm::from_stem(stem.clone())
@@ -643,6 +863,23 @@ mod tests {
             .set_preformative(Some(Preformative::I))
             .print();
         assert_eq!(Ok(String::from("iʔtuku")), verb);
+        let form = FiniteVerbalForm::from_stem(stem.clone())
+            .is_perfective()
+            .is_transitive()
+            .set_subject(Person::FirstSing)
+            .set_object(Person::ThirdSingNonHuman)?
+            .set_preformative(Some(Preformative::I))
+            .print_segmented()?;
+        assert_eq!(String::from("FIN-1SG.A-have"), form.gloss());
+        let form = FiniteVerbalForm::from_stem(stem.clone())
+            .is_imperfective(Some(IpfvStem::Other(String::from("tuktuku"))))
+            .is_transitive()
+            .set_subject(Person::ThirdPlurHuman)
+            .set_object(Person::ThirdSingNonHuman)?
+            .set_preformative(Some(Preformative::I))
+            .print_segmented()?;
+        assert_eq!(String::from("i-b-tuktuku-š"), form.hyphenated());
+        assert_eq!(String::from("FIN-3SG.NH.P-have-3PL.H.A"), form.gloss());
         // I have it
         let verb = FiniteVerbalForm::from_stem(stem.clone())
             .is_imperfective(Some(IpfvStem::Other(String::from("tuktuku"))))
@@ -661,6 +898,52 @@ mod tests {
             .set_preformative(Some(Preformative::I))
             .print();
         assert_eq!(Ok(String::from("iitukun")), verb);
+        // We had it 13.3.1
+        let verb = FiniteVerbalForm::from_stem(stem.clone())
+            .is_perfective()
+            .is_transitive()
+            .set_subject(Person::FirstPlur)
+            .set_object(Person::ThirdSingNonHuman)?
+            .set_preformative(Some(Preformative::I))
+            .print();
+        assert_eq!(Ok(String::from("iʔtukunden")), verb);
+        // You had it 13.3.1
+        let verb = FiniteVerbalForm::from_stem(stem.clone())
+            .is_perfective()
+            .is_transitive()
+            .set_subject(Person::SecondPlur)
+            .set_object(Person::ThirdSingNonHuman)?
+            .set_preformative(Some(Preformative::I))
+            .print();
+        assert_eq!(Ok(String::from("iitukunzen")), verb);
+        // He had us 14.1
+        let verb = FiniteVerbalForm::from_stem(stem.clone())
+            .is_perfective()
+            .is_transitive()
+            .set_subject(Person::ThirdSingHuman)
+            .set_object(Person::FirstPlur)?
+            .set_preformative(Some(Preformative::I))
+            .print();
+        assert_eq!(Ok(String::from("intukunden")), verb);
+        // He has them 13.3.1
+        let verb = FiniteVerbalForm::from_stem(stem.clone())
+            .is_imperfective(Some(IpfvStem::Other(String::from("tuktuku"))))
+            .is_transitive()
+            .set_subject(Person::ThirdSingHuman)
+            .set_object(Person::ThirdPlurHuman)?
+            .set_preformative(Some(Preformative::I))
+            .print();
+        assert_eq!(Ok(String::from("ibtuktukue")), verb);
+        // Which he has, the /e/ is reduced before the nominalizing suffix {ʔa} 14.1
+        let verb = FiniteVerbalForm::from_stem(stem.clone())
+            .is_imperfective(Some(IpfvStem::Other(String::from("tuktuku"))))
+            .is_transitive()
+            .set_subject(Person::ThirdSingHuman)
+            .set_object(Person::ThirdSingNonHuman)?
+            .set_preformative(Some(Preformative::I))
+            .set_subordinator(true)
+            .print();
+        assert_eq!(Ok(String::from("ibtuktukuʔa")), verb);
         // They have it
         let verb = FiniteVerbalForm::from_stem(stem.clone())
             .is_imperfective(Some(IpfvStem::Other(String::from("tuktuku"))))
@@ -670,6 +953,16 @@ mod tests {
             .set_preformative(Some(Preformative::I))
             .print();
         assert_eq!(Ok(String::from("ibtuktukuš")), verb);
+        // They had it 13.3.1
+        let verb = FiniteVerbalForm::from_stem(stem.clone())
+            .is_imperfective(None)
+            .is_transitive()
+            .set_subject(Person::ThirdPlurHuman)
+            .set_object(Person::ThirdSingNonHuman)?
+            .set_preformative(Some(Preformative::I))
+            .is_perfective()
+            .print();
+        assert_eq!(Ok(String::from("intukuš")), verb);
 
         // You do not have it with me 16.2.5 (30)
         let verb = FiniteVerbalForm::from_stem(stem.clone())
@@ -701,7 +994,7 @@ mod tests {
     }
 
     #[test]
-    fn ug() -> Result<(), String> {
+    fn ug() -> Result<(), ConjugationError> {
         // He is dying
         let stem = "ug".to_string();
         let verb = FiniteVerbalForm::from_stem(stem.clone())
@@ -711,6 +1004,24 @@ mod tests {
             .set_preformative(Some(Preformative::I))
             .print();
         assert_eq!(Ok(String::from("uuged")), verb);
+        // He died
+        let verb = FiniteVerbalForm::from_stem(stem.clone())
+            .is_imperfective(Some(IpfvStem::EdMarker))
+            .is_intransitive()
+            .set_subject(Person::ThirdSingHuman)
+            .set_preformative(Some(Preformative::I))
+            .is_perfective()
+            .print();
+        assert_eq!(Ok(String::from("uug")), verb);
+        // Who is dying, the vocalic prefix 24.3 with the nominalizing suffix {ʔa}
+        let verb = FiniteVerbalForm::from_stem(stem.clone())
+            .is_imperfective(Some(IpfvStem::EdMarker))
+            .is_intransitive()
+            .set_subject(Person::ThirdSingHuman)
+            .set_preformative(Some(Preformative::I))
+            .set_subordinator(true)
+            .print();
+        assert_eq!(Ok(String::from("uugedda")), verb);
 
         match verb {
             Err(err) => {
@@ -721,7 +1032,7 @@ mod tests {
     }
 
     #[test]
-    fn zig() -> Result<(), String> {
+    fn zig() -> Result<(), ConjugationError> {
         let stem = "zig".to_string();
         // They were raised from these
         let verb = FiniteVerbalForm::from_stem(stem.clone())
@@ -742,7 +1053,7 @@ mod tests {
     }
 
     #[test]
-    fn zu() -> Result<(), String> {
+    fn zu() -> Result<(), ConjugationError> {
         // He truly knows 22.1 (1)
         let stem = "zu".to_string();
         let verb = FiniteVerbalForm::from_stem(stem.clone())
@@ -761,6 +1072,36 @@ mod tests {
             .set_ventive()
             .print();
         assert_eq!(Ok(String::from("munzu")), verb);
+        // the aspect and the transitivity can be set after the arguments
+        let verb = FiniteVerbalForm::from_stem(stem.clone())
+            .set_subject(Person::ThirdSingHuman)
+            .set_object(Person::ThirdSingNonHuman)?
+            .set_ventive()
+            .is_transitive()
+            .is_perfective()
+            .print();
+        assert_eq!(Ok(String::from("munzu")), verb);
+        let form = FiniteVerbalForm::from_stem(stem.clone())
+            .is_perfective()
+            .is_transitive()
+            .set_subject(Person::ThirdSingHuman)
+            .set_object(Person::ThirdSingNonHuman)?
+            .set_ventive()
+            .print_segmented()?;
+        assert_eq!(String::from("mu-n-zu"), form.hyphenated());
+        assert_eq!(String::from("VEN-3SG.H.A-know"), form.gloss());
+        assert_eq!(
+            vec![
+                MarkerName::Ventive,
+                MarkerName::FinalPersonPrefix,
+                MarkerName::Stem
+            ],
+            form.morphs
+                .iter()
+                .filter(|morph| !morph.surface.is_empty())
+                .map(|morph| morph.slot.clone())
+                .collect::<Vec<MarkerName>>()
+        );
         // I have not known it from you 16.2.4 (27)
         let verb = FiniteVerbalForm::from_stem(stem.clone())
             .is_perfective()
@@ -772,6 +1113,27 @@ mod tests {
             .set_ventive()
             .print();
         assert_eq!(Ok(String::from("numuudaʔzu")), verb);
+        // They knew it 13.3.1
+        let verb = FiniteVerbalForm::from_stem(stem.clone())
+            .is_perfective()
+            .is_transitive()
+            .set_subject(Person::ThirdPlurHuman)
+            .set_object(Person::ThirdSingNonHuman)?
+            .set_ventive()
+            .print();
+        assert_eq!(Ok(String::from("munzuš")), verb);
+        // the plural agent and a first person patient would both need the person-suffix
+        let unsupported = FiniteVerbalForm::from_stem(stem.clone())
+            .is_perfective()
+            .is_transitive()
+            .set_subject(Person::ThirdPlurHuman)
+            .set_object(Person::FirstSing)?
+            .set_preformative(Some(Preformative::I))
+            .print();
+        assert!(matches!(
+            unsupported,
+            Err(ConjugationError::UnsupportedCombination { .. })
+        ));
 
         match verb {
             Err(err) => {
@@ -780,4 +1142,935 @@ mod tests {
             Ok(_) => Ok(()),
         }
     }
+
+    #[test]
+    fn errors() {
+        let verb = FiniteVerbalForm::from_stem("".to_string())
+            .is_perfective()
+            .is_intransitive()
+            .set_subject(Person::ThirdSingHuman)
+            .print();
+        assert_eq!(Err(ConjugationError::EmptyStem), verb);
+
+        let verb = FiniteVerbalForm::from_stem("ĝen".to_string())
+            .is_perfective()
+            .is_intransitive()
+            .set_object(Person::ThirdSingNonHuman)
+            .and_then(|verb| verb.print());
+        assert_eq!(Err(ConjugationError::ObjectOnIntransitive), verb);
+
+        let verb = FiniteVerbalForm::from_stem("ĝar".to_string())
+            .is_perfective()
+            .is_transitive()
+            .set_subject(Person::ThirdSingHuman)
+            .set_oblique_object(Some(Person::ThirdSingNonHuman))
+            .print();
+        assert!(matches!(
+            verb,
+            Err(ConjugationError::UnsupportedCombination { .. })
+        ));
+
+        assert_eq!(
+            Err(ConjugationError::UnknownLexeme("xyz".to_string())),
+            lexicon::find_verb("xyz").map(|verb| verb.stem)
+        );
+    }
+
+    #[test]
+    fn analyze() -> Result<(), ConjugationError> {
+        // He placed it from it 16.2.1 (7)
+        let analyses = analyzer::analyze("ibtanĝar");
+        assert!(analyses
+            .iter()
+            .all(|verb| verb.clone().print() == Ok("ibtanĝar".to_string())));
+        assert!(analyses.iter().any(|verb| {
+            verb.slot_12 == "ĝar"
+                && verb.is_perfective
+                && verb.is_transitive
+                && matches!(verb.subject, Some(Person::ThirdSingHuman))
+                && matches!(verb.object, Some(Person::ThirdSingNonHuman))
+                && matches!(verb.slot_2, Some(Preformative::I))
+                && matches!(verb.slot_6, Some(InitialPersonPrefix::ThirdSingNonHuman))
+                && matches!(verb.slot_9, Some(AdverbialPrefix::Ablative))
+        }));
+        // He erected them in it for him 20.1 (1a)
+        let analyses = analyzer::analyze("munnaninřu");
+        assert!(analyses
+            .iter()
+            .all(|verb| verb.clone().print() == Ok("munnaninřu".to_string())));
+        assert!(analyses.iter().any(|verb| {
+            verb.slot_12 == "řu"
+                && verb.is_perfective
+                && verb.is_transitive
+                && matches!(verb.subject, Some(Person::ThirdSingHuman))
+                // the non-human patients share their segmentation, only the first one is kept
+                && matches!(verb.object, Some(Person::ThirdSingNonHuman))
+                && verb.slot_4.is_some()
+                && matches!(verb.slot_7, Some(IndirectObjectPrefix::ThirdSingHuman))
+                && matches!(verb.slot_10, Some(LocativePrefix::InWithoutInitialPerson))
+        }));
+        // Which he erected for him, the ventive 16.2.5 with the nominalizing suffix {ʔa}
+        let analyses = analyzer::analyze("munnanřuʔa");
+        assert!(analyses.iter().any(|verb| {
+            verb.slot_12 == "řu"
+                && verb.is_perfective
+                && verb.is_transitive
+                && matches!(verb.subject, Some(Person::ThirdSingHuman))
+                && matches!(verb.object, Some(Person::ThirdSingNonHuman))
+                && verb.slot_4.is_some()
+                && matches!(verb.slot_7, Some(IndirectObjectPrefix::ThirdSingHuman))
+                && verb.slot_15
+        }));
+        // He must let it come out of him 16.2.2 (17)
+        let analyses = analyzer::analyze("ḫamuntaʔède");
+        assert!(analyses.iter().any(|verb| {
+            !verb.is_perfective
+                && matches!(verb.slot_1, Some(FirstPrefix::Modal))
+                && verb.slot_4.is_some()
+                && matches!(verb.slot_6, Some(InitialPersonPrefix::ThirdSingHuman))
+                && matches!(verb.slot_9, Some(AdverbialPrefix::Ablative))
+                && verb.slot_10.is_none()
+        }));
+        // the initial person-prefix of a locative
+        let analyses = analyzer::analyze("imminĝar");
+        assert!(analyses.iter().any(|verb| {
+            verb.slot_12 == "ĝar"
+                && verb.is_perfective
+                && verb.is_transitive
+                && matches!(verb.subject, Some(Person::ThirdSingHuman))
+                && matches!(verb.object, Some(Person::ThirdSingNonHuman))
+                && matches!(verb.slot_2, Some(Preformative::I))
+                && verb.slot_4.is_some()
+                && matches!(verb.slot_6, Some(InitialPersonPrefix::ThirdSingNonHuman))
+                && matches!(verb.slot_10, Some(LocativePrefix::OnWithInitialPerson))
+        }));
+        // a configuration is only kept once for each segmentation
+        for surface in ["ibtanĝar", "munnanřuʔa", "imminĝar"] {
+            let forms: Vec<(String, String)> = analyzer::analyze(surface)
+                .into_iter()
+                .map(|mut verb| {
+                    let form = verb.print_segmented().unwrap();
+                    (form.hyphenated(), form.gloss())
+                })
+                .collect();
+            let mut distinct = forms.clone();
+            distinct.sort();
+            distinct.dedup();
+            assert_eq!(forms.len(), distinct.len());
+        }
+        // a stem with no analysis
+        assert!(analyzer::analyze("").is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn dimensional_prefixes() -> Result<(), ConjugationError> {
+        let ĝar = || {
+            let mut verb = FiniteVerbalForm::from_stem("ĝar".to_string());
+            verb.is_perfective()
+                .is_transitive()
+                .set_subject(Person::ThirdSingHuman)
+                .set_preformative(Some(Preformative::I));
+            verb
+        };
+        // the locative with an initial person-prefix keeps its person
+        let mut locative_on =
+            DimensionalPrefixes::with_locative(LocativePrefix::OnWithInitialPerson);
+        locative_on.initial_person = Some(Person::ThirdSingNonHuman);
+        let verb = ĝar()
+            .set_object(Person::ThirdSingNonHuman)?
+            .set_ventive()
+            .set_dimensional_prefixes(&locative_on)?
+            .print();
+        assert_eq!(Ok(String::from("imminĝar")), verb);
+        // and with the indirect object
+        let mut indirect_object = DimensionalPrefixes::with_indirect_object(Person::ThirdSingHuman);
+        indirect_object.initial_person = Some(Person::ThirdSingNonHuman);
+        let verb = ĝar().set_dimensional_prefixes(&indirect_object)?.clone();
+        assert!(matches!(
+            verb.slot_6,
+            Some(InitialPersonPrefix::ThirdSingNonHuman)
+        ));
+        // without a prefix using it, the initial person-prefix is removed
+        let mut locative_in =
+            DimensionalPrefixes::with_locative(LocativePrefix::InWithoutInitialPerson);
+        locative_in.initial_person = Some(Person::ThirdSingNonHuman);
+        let verb = ĝar().set_dimensional_prefixes(&locative_in)?.clone();
+        assert!(verb.slot_6.is_none());
+
+        // the ablative and the terminative fill the same slot
+        let mut adverbial = DimensionalPrefixes::with_ablative_person(Person::ThirdSingNonHuman);
+        adverbial.terminative = true;
+        assert!(matches!(
+            ĝar().set_dimensional_prefixes(&adverbial),
+            Err(ConjugationError::UnsupportedCombination { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn paradigm() -> Result<(), ConjugationError> {
+        let rows = paradigm::Paradigm::from_verb_form(lexicon::find_verb("ĝen")?).rows();
+        assert_eq!(16, rows.len());
+        let surface = |row: &paradigm::ParadigmRow| row.form.clone().map(|form| form.surface());
+        // the plural stems are used with a plural subject 12.4.2
+        let row = rows
+            .iter()
+            .find(|row| row.is_perfective && matches!(row.subject, Person::ThirdPlurHuman))
+            .unwrap();
+        assert_eq!("er", row.stem);
+        assert_eq!(Ok(String::from("ereš")), surface(row));
+        let row = rows
+            .iter()
+            .find(|row| !row.is_perfective && matches!(row.subject, Person::FirstSing))
+            .unwrap();
+        assert_eq!(Ok(String::from("dun")), surface(row));
+
+        let rows = paradigm::Paradigm::from_stem("zu".to_string())
+            .with_objects()
+            .add_dimensional_prefixes(DimensionalPrefixes::with_comitative_person(
+                Person::ThirdSingNonHuman,
+            ))
+            .rows();
+        // intransitive rows have no object
+        assert_eq!(2 * (8 + 8 * 8) * 2, rows.len());
+        let row = rows
+            .iter()
+            .find(|row| {
+                row.is_perfective
+                    && row.is_transitive
+                    && matches!(row.subject, Person::ThirdSingHuman)
+                    && matches!(row.object, Some(Person::ThirdSingNonHuman))
+                    && row.dimensional_prefixes.is_some()
+            })
+            .unwrap();
+        assert_eq!(Ok(String::from("bdanzu")), surface(row));
+
+        Ok(())
+    }
+
+    #[test]
+    fn suppletive_stems() -> Result<(), ConjugationError> {
+        let stem = |verb: &mut FiniteVerbalForm| -> Result<String, ConjugationError> {
+            Ok(verb.resolve_arguments()?.slot_12)
+        };
+        // 12.4.2 ĝen, du, er, sub
+        let mut verb = FiniteVerbalForm::from_lexeme("ĝen")?;
+        verb.is_perfective().set_subject(Person::ThirdSingHuman);
+        assert_eq!("ĝen", stem(&mut verb)?);
+        verb.set_subject(Person::ThirdPlurHuman);
+        assert_eq!("er", stem(&mut verb)?);
+        verb.is_imperfective(None);
+        assert_eq!("sub", stem(&mut verb)?);
+        verb.set_subject(Person::FirstSing);
+        assert_eq!("du", stem(&mut verb)?);
+        assert_eq!(Ok(String::from("dun")), verb.print());
+        // the number of the object decides for transitive verbs
+        let mut verb = FiniteVerbalForm::from_lexeme("re")?;
+        verb.is_perfective()
+            .is_transitive()
+            .set_subject(Person::ThirdPlurHuman)
+            .set_object(Person::ThirdSingNonHuman)?;
+        assert_eq!("re", stem(&mut verb)?);
+        verb.set_object(Person::ThirdPlurNonHuman)?;
+        assert_eq!("tum", stem(&mut verb)?);
+        // an explicit imperfective stem is kept
+        let mut verb = FiniteVerbalForm::from_lexeme("tuš")?;
+        verb.set_subject(Person::ThirdSingHuman)
+            .is_imperfective(None);
+        assert_eq!("dur", stem(&mut verb)?);
+        // a suppletive verb is not reduplicated
+        verb.is_imperfective(Some(IpfvStem::Reduplicate));
+        assert_eq!("dur", stem(&mut verb)?);
+        verb.set_subject(Person::ThirdPlurHuman);
+        assert_eq!("durun", stem(&mut verb)?);
+
+        assert_eq!(
+            Some(ConjugationError::UnknownLexeme("kar".to_string())),
+            FiniteVerbalForm::from_lexeme("kar").err()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn lexicon_transitivity() -> Result<(), ConjugationError> {
+        // the transitivity is taken from the lexicon
+        let verb = FiniteVerbalForm::from_lexeme("zu")?
+            .is_perfective()
+            .set_subject(Person::ThirdSingHuman)
+            .set_object(Person::ThirdSingNonHuman)?
+            .set_ventive()
+            .print();
+        assert_eq!(Ok(String::from("munzu")), verb);
+        let verb = FiniteVerbalForm::from_lexeme("zu")?
+            .is_perfective()
+            .is_intransitive()
+            .set_subject(Person::ThirdPlurHuman)
+            .set_preformative(Some(Preformative::I))
+            .print();
+        assert_eq!(Ok(String::from("izuš")), verb);
+
+        // ĝen is intransitive unless made transitive explicitly
+        assert_eq!(
+            Some(ConjugationError::IntransitiveLexeme("ĝen".to_string())),
+            FiniteVerbalForm::from_lexeme("ĝen")?
+                .set_object(Person::ThirdSingNonHuman)?
+                .print()
+                .err()
+        );
+        assert!(FiniteVerbalForm::from_lexeme("ĝen")?
+            .is_transitive()
+            .set_object(Person::ThirdSingNonHuman)?
+            .print()
+            .is_ok());
+        // the transitivity can be set after the object
+        assert!(FiniteVerbalForm::from_lexeme("ĝen")?
+            .set_object(Person::ThirdSingNonHuman)?
+            .is_transitive()
+            .print()
+            .is_ok());
+        assert_eq!(
+            Some(ConjugationError::IntransitiveLexeme("ĝen".to_string())),
+            FiniteVerbalForm::from_lexeme("ĝen")?
+                .is_transitive()
+                .set_object(Person::ThirdSingNonHuman)?
+                .is_intransitive()
+                .print()
+                .err()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "loader")]
+    fn lexicon_loader() -> Result<(), ConjugationError> {
+        use lexicon::Lexicon;
+
+        let json = r#"{ "verbs": [
+        { "stem": "kar", "meanings": ["to take away", "to flee"], "transitive": true,
+          "citations": ["22.4 (4)"] },
+        { "stem": "ĝar", "meanings": ["to place"], "transitive": true,
+          "sing": { "imperf": "ĝa-ĝa" } }
+    ] }"#;
+        let mut lexicon = Lexicon::builtin();
+        lexicon.merge(Lexicon::from_json(json)?)?;
+        // the missing stems default to the stem
+        let kar = lexicon.find_verb("kar")?;
+        assert_eq!("kar", kar.stem_for(false, true));
+        assert_eq!("take.away", kar.gloss());
+        assert_eq!("ĝa-ĝa", lexicon.find_verb("ĝar")?.stem_for(false, true));
+        assert_eq!(
+            Some("ĝar"),
+            lexicon
+                .find_verb_by_form("ĝa-ĝa")
+                .map(|verb| verb.stem.as_str())
+        );
+        assert!(lexicon.find_verb("ĝen").is_ok());
+
+        let verb = FiniteVerbalForm::from_lexicon_entry(kar)
+            .is_perfective()
+            .set_subject(Person::ThirdSingHuman)
+            .set_object(Person::ThirdSingNonHuman)?
+            .set_preformative(Some(Preformative::I))
+            .print_segmented()?;
+        assert_eq!("FIN-3SG.H.A-take.away", verb.gloss());
+
+        let toml = r#"
+        [[verbs]]
+        stem = "ĝen"
+        meanings = ["to go"]
+        transitive = false
+    "#;
+        assert_eq!(
+            Err(ConjugationError::DuplicateLexeme("ĝen".to_string())),
+            Lexicon::builtin()
+                .merge(Lexicon::from_toml(toml)?)
+                .map(|_| ())
+        );
+        // the entries before the rejected one are not added either
+        let toml = r#"
+        [[verbs]]
+        stem = "kar"
+        meanings = ["to take away"]
+        transitive = true
+
+        [[verbs]]
+        stem = "ĝen"
+        meanings = ["to go"]
+        transitive = false
+    "#;
+        let mut lexicon = Lexicon::builtin();
+        assert_eq!(
+            Err(ConjugationError::DuplicateLexeme("ĝen".to_string())),
+            lexicon.merge(Lexicon::from_toml(toml)?).map(|_| ())
+        );
+        assert_eq!(Lexicon::builtin(), lexicon);
+        assert!(lexicon.find_verb("kar").is_err());
+        let json = r#"{ "verbs": [
+        { "stem": "kar", "meanings": ["to take away"], "transitive": true },
+        { "stem": "kar", "meanings": ["to flee"], "transitive": false }
+    ] }"#;
+        assert_eq!(
+            Err(ConjugationError::DuplicateLexeme("kar".to_string())),
+            Lexicon::from_json(json)
+        );
+        let toml = r#"
+        [[verbs]]
+        stem = "ĝa r"
+        meanings = ["to place"]
+        transitive = true
+    "#;
+        assert_eq!(
+            Err(ConjugationError::MalformedStem("ĝa r".to_string())),
+            Lexicon::from_toml(toml)
+        );
+        assert!(matches!(
+            Lexicon::from_json("{ \"verbs\": [{ \"stem\": \"kar\" }] }"),
+            Err(ConjugationError::InvalidLexicon(_))
+        ));
+
+        // a path of its own, so that parallel runs of the tests do not share it
+        let path = std::env::temp_dir().join(format!(
+            "sumerian-conjugator-lexicon-{}-{}.toml",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos()
+        ));
+        std::fs::write(
+            &path,
+            "[[verbs]]\nstem = \"kar\"\nmeanings = [\"to take away\"]\ntransitive = true\n",
+        )
+        .map_err(|err| ConjugationError::InvalidLexicon(err.to_string()))?;
+        let lexicon = Lexicon::load(&path)?;
+        assert_eq!(12, lexicon.verbs.len());
+        // the verbs are built from the loaded lexicon
+        let verb = lexicon
+            .verb("kar")?
+            .is_perfective()
+            .set_subject(Person::ThirdSingHuman)
+            .set_object(Person::ThirdSingNonHuman)?
+            .print_segmented()?;
+        assert_eq!("3SG.H.A-take.away", verb.gloss());
+        // the built-in verbs have no citation
+        assert!(lexicon.find_verb("ĝen")?.citations.is_empty());
+        std::fs::remove_file(&path)
+            .map_err(|err| ConjugationError::InvalidLexicon(err.to_string()))?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn meaning_lookup() -> Result<(), ConjugationError> {
+        let stems = |verbs: Vec<&lexicon::LexiconEntry>| -> Vec<String> {
+            verbs.iter().map(|verb| verb.stem.to_string()).collect()
+        };
+        let builtin = lexicon::Lexicon::builtin();
+        assert_eq!(vec!["ĝen"], stems(builtin.find_verbs_by_meaning("Go")));
+        assert_eq!(vec!["ĝen"], stems(builtin.find_verbs_by_meaning("to go")));
+        // every meaning of an entry is looked at
+        assert_eq!(vec!["zig"], stems(builtin.find_verbs_by_meaning("RAISE")));
+        assert_eq!(vec!["hulu"], stems(builtin.find_verbs_by_meaning("bad")));
+        assert_eq!(vec!["tuku"], stems(builtin.find_verbs_by_meaning("hav")));
+        assert!(builtin.find_verbs_by_meaning("").is_empty());
+        assert!(builtin.find_verbs_by_meaning("swim").is_empty());
+
+        // exact matches, then word matches, then substrings
+        let mut lexicon = lexicon::Lexicon::builtin();
+        lexicon.merge(lexicon::Lexicon {
+            verbs: vec![
+                lexicon::LexiconEntry {
+                    stem: "řu".to_string(),
+                    meanings: vec!["to erect".to_string(), "to set up".to_string()],
+                    transitive: true,
+                    sing: lexicon::AspectStems::default(),
+                    plur: lexicon::AspectStems::default(),
+                    maru: None,
+                    citations: vec!["20.1 (1a)".to_string()],
+                },
+                lexicon::LexiconEntry {
+                    stem: "ĝar".to_string(),
+                    meanings: vec!["to place".to_string(), "to set".to_string()],
+                    transitive: true,
+                    sing: lexicon::AspectStems::default(),
+                    plur: lexicon::AspectStems::default(),
+                    maru: None,
+                    citations: vec!["16.2.1 (7)".to_string()],
+                },
+            ],
+        })?;
+        assert_eq!(
+            vec!["ĝar", "řu"],
+            stems(lexicon.find_verbs_by_meaning("set"))
+        );
+        assert_eq!(vec!["řu"], stems(lexicon.find_verbs_by_meaning("Set Up")));
+        assert_eq!(
+            vec!["zig", "gi", "řu", "ĝar"],
+            stems(lexicon.find_verbs_by_meaning("se"))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn maru_classes() -> Result<(), ConjugationError> {
+        use lexicon::MaruClass;
+
+        // 12.4.3 the imperfective stems of the lexicon follow their class
+        for verb in lexicon::VERBFORMS.iter() {
+            if let Some(imperf) = verb.maru.and_then(|class| class.derive(verb.stem)) {
+                assert_eq!(verb.sing.imperf, imperf);
+            }
+        }
+        assert_eq!(
+            Some("hulhulu".to_string()),
+            MaruClass::PartialReduplication.derive("hulu")
+        );
+        assert_eq!(
+            Some("zig-zig".to_string()),
+            MaruClass::FullReduplication.derive("zig")
+        );
+        assert_eq!(None, MaruClass::Suppletive.derive("ĝen"));
+        // the long and accented vowels, precomposed or not, are vowels too
+        assert_eq!(
+            Some("tūktūku".to_string()),
+            MaruClass::PartialReduplication.derive("tūku")
+        );
+        assert_eq!(
+            Some("ḗlḗla".to_string()),
+            MaruClass::PartialReduplication.derive("ḗla")
+        );
+        assert_eq!(
+            Some("tu\u{304}ktu\u{304}ku".to_string()),
+            MaruClass::PartialReduplication.derive("tu\u{304}ku")
+        );
+        // no consonant follows the first vowel
+        assert_eq!(None, MaruClass::PartialReduplication.derive("gi"));
+        assert_eq!(None, MaruClass::PartialReduplication.derive(""));
+
+        // the builder reduplicates the stem of a lexicon verb according to its class
+        let verb = FiniteVerbalForm::from_lexeme("tuku")?
+            .set_subject(Person::ThirdPlurHuman)
+            .set_object(Person::ThirdSingNonHuman)?
+            .set_preformative(Some(Preformative::I))
+            .is_imperfective(Some(IpfvStem::Reduplicate))
+            .print();
+        assert_eq!(Ok(String::from("ibtuktukuš")), verb);
+        // the lexicon, its class and a stem outside of the lexicon reduplicate gi alike
+        let gi = |verb: &mut FiniteVerbalForm| {
+            verb.set_subject(Person::ThirdSingHuman)
+                .set_object(Person::ThirdSingNonHuman)?
+                .set_preformative(Some(Preformative::I))
+                .is_imperfective(Some(IpfvStem::Reduplicate))
+                .print()
+        };
+        assert_eq!(
+            Ok(String::from("ibgi-gie")),
+            gi(&mut FiniteVerbalForm::from_lexeme("gi")?)
+        );
+        assert_eq!(
+            Ok(String::from("ibgi-gie")),
+            gi(FiniteVerbalForm::from_stem("gi".to_string()).is_transitive())
+        );
+        // a stem of the lexicon that its class cannot reduplicate is not repeated as a whole
+        let entry = lexicon::LexiconEntry {
+            stem: "gi".to_string(),
+            meanings: vec!["to return".to_string()],
+            transitive: true,
+            sing: lexicon::AspectStems {
+                perf: "gi".to_string(),
+                imperf: "gi".to_string(),
+            },
+            plur: lexicon::AspectStems {
+                perf: "gi".to_string(),
+                imperf: "gi".to_string(),
+            },
+            maru: Some(MaruClass::PartialReduplication),
+            citations: Vec::new(),
+        };
+        assert_eq!(
+            Err(ConjugationError::MalformedStem("gi".to_string())),
+            gi(&mut FiniteVerbalForm::from_lexicon_entry(&entry))
+        );
+
+        #[cfg(feature = "loader")]
+        {
+            let toml = r#"
+            [[verbs]]
+            stem = "zig"
+            meanings = ["to rise"]
+            transitive = false
+            maru = "full_reduplication"
+            [[verbs]]
+            stem = "ĝen"
+            meanings = ["to go"]
+            transitive = false
+            maru = "suppletive"
+        "#;
+            assert!(matches!(
+                lexicon::Lexicon::from_toml(toml),
+                Err(ConjugationError::InvalidLexicon(_))
+            ));
+            let lexicon = lexicon::Lexicon::from_toml(&toml.replace(
+            "maru = \"suppletive\"",
+            "maru = \"suppletive\"\n[verbs.sing]\nimperf = \"du\"\n[verbs.plur]\nperf = \"er\"\nimperf = \"sub\"",
+        ))?;
+            let zig = lexicon.find_verb("zig")?;
+            assert_eq!("zig-zig", zig.stem_for(false, false));
+            assert_eq!("zig-zig", zig.stem_for(false, true));
+            let verb = FiniteVerbalForm::from_lexicon_entry(zig)
+                .set_subject(Person::ThirdSingHuman)
+                .set_preformative(Some(Preformative::I))
+                .is_imperfective(None)
+                .print();
+            assert_eq!(Ok(String::from("izig-zig")), verb);
+            assert_eq!("sub", lexicon.find_verb("ĝen")?.stem_for(false, true));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn wasm_verb() {
+        // the errors can only be built in JavaScript, the forms are checked here
+        let mut verb = wasm::Verb::new("zu".to_string());
+        verb.perfective();
+        verb.transitive();
+        verb.set_subject(Person::ThirdSingHuman);
+        assert!(verb.set_object(Person::ThirdSingNonHuman).is_ok());
+        verb.set_ventive(true);
+        assert_eq!(Some(String::from("munzu")), verb.print().ok());
+        assert_eq!(Some(String::from("mu-n-zu")), verb.hyphenated().ok());
+        assert_eq!(Some(String::from("VEN-3SG.H.A-know")), verb.gloss().ok());
+        // the markers of the slots 1 and 4 can be unset
+        verb.set_negative(true);
+        assert_eq!(Some(String::from("numunzu")), verb.print().ok());
+        verb.set_modal(false);
+        assert_eq!(Some(String::from("numunzu")), verb.print().ok());
+        verb.set_negative(false);
+        assert_eq!(Some(String::from("munzu")), verb.print().ok());
+        verb.set_modal(true);
+        assert_eq!(Some(String::from("ḫamunzu")), verb.print().ok());
+        verb.set_negative(false);
+        assert_eq!(Some(String::from("ḫamunzu")), verb.print().ok());
+        verb.set_modal(false);
+        verb.set_ventive(false);
+        assert_eq!(Some(String::from("nzu")), verb.print().ok());
+
+        let mut verb = wasm::Verb::new("ak".to_string());
+        verb.perfective();
+        verb.set_subject(Person::ThirdSingHuman);
+        verb.set_comitative(Some(Person::ThirdSingNonHuman));
+        verb.set_preformative(Some(Preformative::A));
+        assert_eq!(Some(String::from("abdaak")), verb.print().ok());
+
+        // the dimensional prefixes can be set and removed
+        let mut verb = wasm::Verb::new("ĝar".to_string());
+        verb.perfective();
+        verb.transitive();
+        verb.set_subject(Person::ThirdSingHuman);
+        assert!(verb.set_object(Person::ThirdSingNonHuman).is_ok());
+        verb.set_preformative(Some(Preformative::I));
+        verb.set_initial_person_prefix(Some(Person::ThirdSingNonHuman));
+        verb.set_adverbial_prefix(Some(AdverbialPrefix::Ablative));
+        assert_eq!(Some(String::from("ibtanĝar")), verb.print().ok());
+        verb.set_indirect_object(Some(Person::ThirdSingHuman));
+        let with_indirect_object = verb.print().ok();
+        verb.set_indirect_object(None);
+        assert_eq!(Some(String::from("ibtanĝar")), verb.print().ok());
+        assert_ne!(with_indirect_object, verb.print().ok());
+        assert!(verb.clear_dimensional_prefixes().is_ok());
+        assert_eq!(Some(String::from("inĝar")), verb.print().ok());
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn json_specification() {
+        let result = json::conjugate(
+            r#"{ "stem": "zu", "perfective": true, "transitive": true,
+             "subject": "3sg.h", "object": "3sg.nh", "ventive": true }"#,
+        );
+        assert_eq!(Some(String::from("munzu")), result.surface);
+        assert_eq!(Some(String::from("mu-n-zu")), result.hyphenated);
+        assert_eq!(Some(String::from("VEN-3SG.H.A-know")), result.gloss);
+        assert_eq!(
+            json::MorphResult {
+                slot: "final_person_prefix".to_string(),
+                morpheme: "n".to_string(),
+                surface: "n".to_string(),
+                gloss: "3SG.H.A".to_string(),
+            },
+            result.segmentation[1]
+        );
+        assert!(result.errors.is_empty());
+
+        // He erected them in it for him 20.1 (1a)
+        let result = json::conjugate(
+            r#"{ "stem": "řu", "perfective": true, "transitive": true,
+             "subject": "3sg.h", "object": "3pl.nh", "ventive": true,
+             "dimensional_prefixes": { "indirect_object": "3sg.h", "locative": "in" } }"#,
+        );
+        assert_eq!(Some(String::from("munnaninřu")), result.surface);
+        // the stems are taken from the lexicon
+        let result = json::conjugate(
+            r#"{ "stem": "ĝen", "from_lexicon": true, "perfective": true,
+             "subject": "3pl.h", "preformative": "i" }"#,
+        );
+        assert_eq!(Some(String::from("iereš")), result.surface);
+
+        let result = json::conjugate(r#"{ "stem": "ĝen", "subject": "3sg" }"#);
+        assert_eq!(None, result.surface);
+        assert_eq!(
+            vec!["Invalid verb specification: unknown person '3sg'".to_string()],
+            result.errors
+        );
+        let result = json::conjugate(r#"{ "stem": "", "subject": "3sg.h" }"#);
+        assert_eq!(vec!["Stem cannot be empty".to_string()], result.errors);
+        assert!(json::conjugate_json("{ \"stem\": ")
+            .is_ok_and(|result| result.contains("\"surface\":null")));
+        // every error of the specification is reported
+        let result = json::conjugate(
+            r#"{ "stem": "zu", "subject": "3sg", "object": "4pl", "preformative": "o" }"#,
+        );
+        assert_eq!(3, result.errors.len());
+        // the locative keeps its initial person-prefix
+        let result = json::conjugate(
+            r#"{ "stem": "ĝar", "perfective": true, "transitive": true,
+             "subject": "3sg.h", "object": "3sg.nh", "preformative": "i", "ventive": true,
+             "dimensional_prefixes": { "locative": "on_with_initial_person",
+                                       "initial_person": "3sg.nh" } }"#,
+        );
+        assert_eq!(Some(String::from("imminĝar")), result.surface);
+        let result = json::conjugate(
+            r#"{ "stem": "ĝar", "perfective": true, "transitive": true,
+             "subject": "3sg.h", "preformative": "i",
+             "dimensional_prefixes": { "locative": "in_with_initial_person",
+                                       "initial_person": "3sg.nh" } }"#,
+        );
+        assert!(result.errors.is_empty());
+        assert_eq!(Some(String::from("i-b-n-ĝar")), result.hyphenated);
+        let result = json::conjugate(
+            r#"{ "stem": "ĝar", "dimensional_prefixes": { "locative": "in_with_initial_person" } }"#,
+        );
+        assert_eq!(
+            vec!["Invalid verb specification: the locative with an initial person needs an initial person".to_string()],
+            result.errors
+        );
+        let result = json::conjugate(
+            r#"{ "stem": "ĝar", "dimensional_prefixes": { "ablative": true, "terminative": true,
+                                                         "initial_person": "3sg.nh" } }"#,
+        );
+        assert_eq!(1, result.errors.len());
+        let result = json::conjugate(r#"{ "stem": "zu", "negative": true, "modal": true }"#);
+        assert_eq!(None, result.surface);
+        assert_eq!(
+            vec![
+                "Invalid verb specification: a verb cannot be both negative and modal".to_string()
+            ],
+            result.errors
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "serde", feature = "json"))]
+    fn serde_round_trip() -> Result<(), ConjugationError> {
+        let mut verb = FiniteVerbalForm::from_lexeme("zu")?;
+        verb.is_perfective()
+            .set_subject(Person::ThirdSingHuman)
+            .set_object(Person::ThirdSingNonHuman)?
+            .set_ventive()
+            .set_preformative(Some(Preformative::I))
+            .set_dimensional_prefixes(&DimensionalPrefixes::with_locative(
+                LocativePrefix::InWithoutInitialPerson,
+            ))?;
+        let serialized = serde_json::to_string(&verb).unwrap();
+        assert!(serialized.contains(r#""subject":"3sg.h""#));
+        assert!(serialized.contains(r#""object":"3sg.nh""#));
+        assert!(serialized.contains(r#""slot_2":"i""#));
+        assert!(serialized.contains(r#""slot_3":false"#));
+        assert!(serialized.contains(r#""slot_4":true"#));
+        assert!(serialized.contains(r#""slot_10":"in_without_initial_person""#));
+        assert!(serialized.contains(r#""maru":null"#));
+        let mut deserialized: FiniteVerbalForm = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(verb.print(), deserialized.print());
+        assert!(deserialized.slot_3.is_none());
+
+        let prefixes: DimensionalPrefixes = serde_json::from_str(
+            r#"{ "indirect_object": null, "comitative": true, "locative": null,
+             "ablative": false, "terminative": false, "initial_person": "1sg" }"#,
+        )
+        .unwrap();
+        assert!(matches!(prefixes.initial_person, Some(Person::FirstSing)));
+        assert_eq!(
+            r#"["ablative","terminative"]"#,
+            serde_json::to_string(&[AdverbialPrefix::Ablative, AdverbialPrefix::Terminative])
+                .unwrap()
+        );
+
+        let serialized = serde_json::to_string(lexicon::find_verb("tuku")?).unwrap();
+        assert!(serialized.contains(r#""maru":"partial_reduplication""#));
+        // the entries are read back as owned LexiconEntry, even with escaped characters
+        let entry = lexicon::LexiconEntry::from(lexicon::find_verb("ĝen")?);
+        let serialized = serde_json::to_string(&entry)
+            .unwrap()
+            .replace('ĝ', "\\u011d");
+        assert!(serialized.contains("\\u011den"));
+        let deserialized: lexicon::LexiconEntry = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(entry, deserialized);
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "cli")]
+    fn command_line() -> Result<(), ConjugationError> {
+        let args = |line: &str| {
+            line.split_whitespace()
+                .map(String::from)
+                .collect::<Vec<String>>()
+        };
+
+        let options = cli::parse_args(args(
+            "--perfective zu --transitive --subject 3sg.h --object 3sg.nh --ventive -s --gloss",
+        ))?;
+        assert_eq!("zu", options.stem);
+        assert!(options.segmented && options.gloss);
+        let form = options.verb()?.print_segmented()?;
+        assert_eq!("munzu", form.surface());
+        assert_eq!("mu-n-zu", form.hyphenated());
+
+        // He erected them in it for him 20.1 (1a)
+        let verb = cli::parse_args(args(
+        "řu --perfective --transitive --subject 3sg.h --object 3pl.nh --dative 3sg.h --locative in --ventive",
+    ))?
+    .verb()?
+    .print();
+        assert_eq!(Ok(String::from("munnaninřu")), verb);
+        let verb = cli::parse_args(args(
+            "ĝen --lexicon --perfective --subject 3pl.h --preformative i",
+        ))?
+        .verb()?
+        .print();
+        assert_eq!(Ok(String::from("iereš")), verb);
+        // He placed it there towards me 22.4, the initial person of the locative
+        // can be given before or after it
+        let placed = "ĝar --perfective --transitive --subject 3sg.h --object 3sg.nh --ventive --preformative i";
+        for locative in [
+            "--locative on_with_initial_person --initial-person 3sg.nh",
+            "--initial-person 3sg.nh --locative on_with_initial_person",
+        ] {
+            let verb = cli::parse_args(args(&format!("{} {}", placed, locative)))?
+                .verb()?
+                .print();
+            assert_eq!(Ok(String::from("imminĝar")), verb);
+        }
+        assert_eq!(
+            Some(ConjugationError::InvalidSpecification(
+                "the locative with an initial person needs --initial-person".to_string()
+            )),
+            cli::parse_args(args(&format!(
+                "{} --locative in_with_initial_person",
+                placed
+            )))?
+            .verb()
+            .err()
+        );
+
+        assert_eq!(
+            Some(ConjugationError::InvalidSpecification(
+                "unknown command 'plural'".to_string()
+            )),
+            cli::parse_args(args("ak --plural")).err()
+        );
+        assert_eq!(
+            Some(ConjugationError::InvalidSpecification(
+                "'--subject' needs a value".to_string()
+            )),
+            cli::parse_args(args("ak --subject")).err()
+        );
+        assert!(matches!(
+            cli::parse_args(args("ak --preformative e"))?.verb(),
+            Err(ConjugationError::InvalidSpecification(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "cli")]
+    fn interactive_session() -> Result<(), ConjugationError> {
+        let mut session = repl::Session::default();
+        assert!(session.execute("ventive").is_err());
+
+        session.execute("stem zu")?;
+        session.execute("perfective")?;
+        session.execute("transitive")?;
+        assert_eq!("nzu  n-zu  3SG.H.A-know", session.execute("subject 3sg.h")?);
+        assert_eq!(
+            "munzu  mu-n-zu  VEN-3SG.H.A-know",
+            session.execute("ventive")?
+        );
+        session.execute("save")?;
+        // the markers are toggled
+        assert_eq!("nzu  n-zu  3SG.H.A-know", session.execute("ventive")?);
+        assert_eq!("zu  zu  know", session.execute("subject none")?);
+        session.execute("undo")?;
+        assert_eq!("munzu  mu-n-zu  VEN-3SG.H.A-know", session.execute("undo")?);
+        assert_eq!(
+            "1. munzu  mu-n-zu  VEN-3SG.H.A-know\n",
+            session.execute("saved")?
+        );
+
+        assert_eq!(
+            Err(ConjugationError::InvalidSpecification(
+                "unknown command 'plural'".to_string()
+            )),
+            session.execute("plural")
+        );
+        // the negative and the modal prefixes replace each other in the slot 1
+        let negative = "numunzu  nu-mu-n-zu  NEG-VEN-3SG.H.A-know";
+        assert_eq!(negative, session.execute("negative")?);
+        assert_eq!(
+            "ḫamunzu  ḫa-mu-n-zu  MOD-VEN-3SG.H.A-know",
+            session.execute("modal")?
+        );
+        assert_eq!(negative, session.execute("negative")?);
+        assert_eq!(negative, session.execute("modal none")?);
+        assert_eq!(
+            "munzu  mu-n-zu  VEN-3SG.H.A-know",
+            session.execute("negative")?
+        );
+
+        // the ed marker is toggled and cleared
+        session.execute("stem ug")?;
+        let ed = "uged  ug-ed  ug-PF";
+        assert_eq!(ed, session.execute("ed")?);
+        assert_eq!("ug  ug  ug", session.execute("ed")?);
+        assert_eq!(ed, session.execute("ed")?);
+        assert_eq!("ug  ug  ug", session.execute("ed none")?);
+
+        // the initial person-prefix stays with the remaining dimensional prefix
+        session.execute("stem ĝar")?;
+        session.execute("perfective")?;
+        session.execute("transitive")?;
+        session.execute("subject 3sg.h")?;
+        session.execute("preformative i")?;
+        let comitative = "ibdanĝar  i-b-da-n-ĝar  FIN-3SG.NH-COM-3SG.H.A-ĝar";
+        assert_eq!(comitative, session.execute("comitative 3sg.nh")?);
+        session.execute("ablative 3sg.nh")?;
+        assert_eq!(comitative, session.execute("ablative none")?);
+        assert_eq!(
+            "inĝar  i-n-ĝar  FIN-3SG.H.A-ĝar",
+            session.execute("comitative none")?
+        );
+
+        session.execute("quit")?;
+        assert!(session.finished);
+        Ok(())
+    }
 }
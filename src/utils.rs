@@ -1,3 +1,4 @@
+#[allow(dead_code)]
 pub fn starts_with_consonant(s: &str) -> bool {
     let consonants = vec![
         'b', 'd', 'f', 'g', 'ĝ', 'h', 'k', 'l', 'm', 'n', 'r', 'ř', 's', 'š', 't', 'z',
@@ -9,6 +10,15 @@ pub fn starts_with_consonant(s: &str) -> bool {
     }
 }
 
+// the vowels with their length and accent marks, e.g. ā, ḗ, è
+pub fn is_vowel(letter: char) -> bool {
+    let vowels = vec![
+        'a', 'e', 'i', 'u', 'á', 'à', 'â', 'ā', 'é', 'è', 'ê', 'ē', 'ḗ', 'í', 'ì', 'î', 'ī', 'ú',
+        'ù', 'û', 'ū',
+    ];
+    vowels.contains(&letter)
+}
+
 pub fn ends_with_vowel(s: &str) -> bool {
     let vowels = vec!['a', 'e', 'i', 'u', 'è', 'é', 'á', 'ā', 'ē', 'ī', 'ū'];
     if let Some(last_char) = s.chars().last() {
@@ -17,3 +27,14 @@ pub fn ends_with_vowel(s: &str) -> bool {
         false // Return false if the string is empty
     }
 }
+
+pub fn ends_with_consonant(s: &str) -> bool {
+    let consonants = vec![
+        'b', 'd', 'f', 'g', 'ĝ', 'h', 'k', 'l', 'm', 'n', 'r', 'ř', 's', 'š', 't', 'z',
+    ];
+    if let Some(last_char) = s.chars().last() {
+        consonants.contains(&last_char)
+    } else {
+        false // Return false if the string is empty
+    }
+}
@@ -0,0 +1,207 @@
+use crate::{
+    AdverbialPrefix, ConjugationError, Coordinator, DimensionalPrefixes, FiniteVerbalForm,
+    FirstPrefix, IpfvStem, MiddlePrefix, Person, Preformative,
+};
+use wasm_bindgen::prelude::*;
+
+/*
+    JavaScript API, wrapping FiniteVerbalForm for wasm-pack.
+    The setters mirror those of FiniteVerbalForm under camelCase names,
+    the errors are thrown as JavaScript errors.
+*/
+
+#[wasm_bindgen]
+pub struct Verb {
+    verb: FiniteVerbalForm,
+}
+
+fn js_error(error: ConjugationError) -> JsError {
+    JsError::new(&error.to_string())
+}
+
+#[wasm_bindgen]
+impl Verb {
+    #[wasm_bindgen(constructor)]
+    pub fn new(stem: String) -> Verb {
+        Verb {
+            verb: FiniteVerbalForm::from_stem(stem),
+        }
+    }
+    #[wasm_bindgen(js_name = fromLexeme)]
+    pub fn from_lexeme(stem: &str) -> Result<Verb, JsError> {
+        Ok(Verb {
+            verb: FiniteVerbalForm::from_lexeme(stem).map_err(js_error)?,
+        })
+    }
+
+    pub fn transitive(&mut self) {
+        self.verb.is_transitive();
+    }
+    pub fn intransitive(&mut self) {
+        self.verb.is_intransitive();
+    }
+    pub fn perfective(&mut self) {
+        self.verb.is_perfective();
+    }
+    pub fn imperfective(&mut self) {
+        self.verb.is_imperfective(None);
+    }
+    #[wasm_bindgen(js_name = imperfectiveReduplicated)]
+    pub fn imperfective_reduplicated(&mut self) {
+        self.verb.is_imperfective(Some(IpfvStem::Reduplicate));
+    }
+    #[wasm_bindgen(js_name = imperfectiveWithEd)]
+    pub fn imperfective_with_ed(&mut self) {
+        self.verb.is_imperfective(Some(IpfvStem::EdMarker));
+    }
+    #[wasm_bindgen(js_name = imperfectiveStem)]
+    pub fn imperfective_stem(&mut self, stem: String) {
+        self.verb.is_imperfective(Some(IpfvStem::Other(stem)));
+    }
+
+    #[wasm_bindgen(js_name = setSubject)]
+    pub fn set_subject(&mut self, subject: Person) {
+        self.verb.set_subject(subject);
+    }
+    #[wasm_bindgen(js_name = setObject)]
+    pub fn set_object(&mut self, object: Person) -> Result<(), JsError> {
+        self.verb.set_object(object).map_err(js_error)?;
+        Ok(())
+    }
+    #[wasm_bindgen(js_name = setObliqueObject)]
+    pub fn set_oblique_object(&mut self, oblique_object: Option<Person>) {
+        self.verb.set_oblique_object(oblique_object);
+    }
+
+    // the negative and the modal prefixes replace each other in the slot 1,
+    // unsetting one of them keeps the other
+    #[wasm_bindgen(js_name = setNegative)]
+    pub fn set_negative(&mut self, negative: bool) {
+        match negative {
+            true => {
+                self.verb.set_negative();
+            }
+            false => {
+                if matches!(self.verb.slot_1, Some(FirstPrefix::Negative)) {
+                    self.verb.slot_1 = None;
+                }
+            }
+        }
+    }
+    #[wasm_bindgen(js_name = setModal)]
+    pub fn set_modal(&mut self, modal: bool) {
+        match modal {
+            true => {
+                self.verb.set_modal();
+            }
+            false => {
+                if matches!(self.verb.slot_1, Some(FirstPrefix::Modal)) {
+                    self.verb.slot_1 = None;
+                }
+            }
+        }
+    }
+    #[wasm_bindgen(js_name = setPreformative)]
+    pub fn set_preformative(&mut self, preformative: Option<Preformative>) {
+        self.verb.set_preformative(preformative);
+    }
+    #[wasm_bindgen(js_name = setCoordinator)]
+    pub fn set_coordinator(&mut self, coordinator: bool) {
+        self.verb
+            .set_coordinator(coordinator.then_some(Coordinator));
+    }
+    #[wasm_bindgen(js_name = setVentive)]
+    pub fn set_ventive(&mut self, ventive: bool) {
+        match ventive {
+            true => {
+                self.verb.set_ventive();
+            }
+            false => self.verb.slot_4 = None,
+        }
+    }
+    #[wasm_bindgen(js_name = setMiddlePrefix)]
+    pub fn set_middle_prefix(&mut self, middle_prefix: bool) {
+        self.verb
+            .set_middle_prefix(middle_prefix.then_some(MiddlePrefix));
+    }
+    #[wasm_bindgen(js_name = setInitialPersonPrefix)]
+    pub fn set_initial_person_prefix(&mut self, initial_person_prefix: Option<Person>) {
+        self.verb.set_initial_person_prefix(initial_person_prefix);
+    }
+    #[wasm_bindgen(js_name = setIndirectObject)]
+    pub fn set_indirect_object(&mut self, indirect_object: Option<Person>) {
+        match indirect_object {
+            Some(person) => {
+                self.verb.set_indirect_object(person);
+            }
+            None => self.verb.slot_7 = None,
+        }
+    }
+    #[wasm_bindgen(js_name = setComitative)]
+    pub fn set_comitative(&mut self, initial_person_prefix: Option<Person>) {
+        self.verb.set_comitative(initial_person_prefix);
+    }
+    #[wasm_bindgen(js_name = setAblative)]
+    pub fn set_ablative(&mut self, initial_person_prefix: Option<Person>) {
+        self.verb.set_ablative(initial_person_prefix);
+    }
+    #[wasm_bindgen(js_name = setTerminative)]
+    pub fn set_terminative(&mut self, initial_person_prefix: Option<Person>) {
+        self.verb.set_terminative(initial_person_prefix);
+    }
+    #[wasm_bindgen(js_name = setAdverbialPrefix)]
+    pub fn set_adverbial_prefix(&mut self, adverbial_prefix: Option<AdverbialPrefix>) {
+        self.verb.set_adverbial_prefix(adverbial_prefix);
+    }
+    // removes the indirect object, the comitative, the adverbial and the locative prefixes
+    #[wasm_bindgen(js_name = clearDimensionalPrefixes)]
+    pub fn clear_dimensional_prefixes(&mut self) -> Result<(), JsError> {
+        self.verb
+            .set_dimensional_prefixes(&DimensionalPrefixes::all_false())
+            .map_err(js_error)?;
+        Ok(())
+    }
+    #[wasm_bindgen(js_name = setLocativeIn)]
+    pub fn set_locative_in(&mut self, initial_person: Option<Person>) {
+        self.verb.set_locative_in(initial_person);
+    }
+    #[wasm_bindgen(js_name = setLocativeOn)]
+    pub fn set_locative_on(&mut self, initial_person: Option<Person>) {
+        self.verb.set_locative_on(initial_person);
+    }
+    #[wasm_bindgen(js_name = setFinalPersonPrefix)]
+    pub fn set_final_person_prefix(&mut self, final_person_prefix: Option<Person>) {
+        self.verb.set_final_person_prefix(final_person_prefix);
+    }
+    #[wasm_bindgen(js_name = setEdMarker)]
+    pub fn set_ed_marker(&mut self, ed_marker: bool) {
+        self.verb.set_ed_marker(ed_marker.then(|| "ed".to_string()));
+    }
+    #[wasm_bindgen(js_name = setFinalPersonSuffix)]
+    pub fn set_final_person_suffix(&mut self, final_person_suffix: Option<Person>) {
+        self.verb.set_final_person_suffix(final_person_suffix);
+    }
+    #[wasm_bindgen(js_name = setSubordinator)]
+    pub fn set_subordinator(&mut self, subordinator: bool) {
+        self.verb.set_subordinator(subordinator);
+    }
+
+    pub fn print(&mut self) -> Result<String, JsError> {
+        self.verb.print().map_err(js_error)
+    }
+    // e.g. "mu-n-zu"
+    pub fn hyphenated(&mut self) -> Result<String, JsError> {
+        Ok(self.verb.print_segmented().map_err(js_error)?.hyphenated())
+    }
+    // e.g. "VEN-3SG.H.A-know"
+    pub fn gloss(&mut self) -> Result<String, JsError> {
+        Ok(self.verb.print_segmented().map_err(js_error)?.gloss())
+    }
+}
+
+// Takes a verb specification in JSON and returns the result in JSON, see json::conjugate_json
+#[cfg(feature = "json")]
+#[wasm_bindgen(js_name = conjugateJson)]
+pub fn conjugate_json(specification: &str) -> Result<String, JsError> {
+    crate::json::conjugate_json(specification).map_err(js_error)
+}